@@ -0,0 +1,120 @@
+// renderer-headless/src/main.rs
+
+use simulation::{Grid, GridStats, CellState};
+use std::process;
+
+const USAGE: &str = "Usage: renderer-headless [--width N] [--height N] [--seed N] [--steps N] [--every N]
+                          [--food-production-prob PERCENT] [--max-organisms N]";
+
+/// Command line settings for a headless run
+struct Args {
+    width: u32,
+    height: u32,
+    seed: u64,
+    steps: u32,
+    every: u32,                 // Print a CSV row every this many steps
+    food_production_prob: Option<f32>,
+    max_organisms: Option<usize>,
+}
+
+impl Args {
+    fn parse() -> Result<Args, String> {
+        let mut args = Args {
+            width: 100,
+            height: 100,
+            seed: 0,
+            steps: 1000,
+            every: 10,
+            food_production_prob: None,
+            max_organisms: None,
+        };
+        
+        let mut iter = std::env::args().skip(1);
+        while let Some(flag) = iter.next() {
+            if flag == "--help" || flag == "-h" {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            let value = iter.next().ok_or_else(|| format!("missing value for {}", flag))?;
+            match flag.as_str() {
+                "--width" => args.width = parse_value(&flag, &value)?,
+                "--height" => args.height = parse_value(&flag, &value)?,
+                "--seed" => args.seed = parse_value(&flag, &value)?,
+                "--steps" => args.steps = parse_value(&flag, &value)?,
+                "--every" => args.every = parse_value(&flag, &value)?,
+                "--food-production-prob" => args.food_production_prob = Some(parse_value(&flag, &value)?),
+                "--max-organisms" => args.max_organisms = Some(parse_value(&flag, &value)?),
+                _ => return Err(format!("unknown argument {}", flag)),
+            }
+        }
+        
+        if args.width == 0 || args.height == 0 {
+            return Err("width and height must be at least 1".to_string());
+        }
+        args.every = args.every.max(1);
+        Ok(args)
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value '{}' for {}", value, flag))
+}
+
+/// Organism cell types reported as CSV columns, in order
+const CELL_COLUMNS: [(CellState, &str); 7] = [
+    (CellState::Mouth, "mouth_cells"),
+    (CellState::Producer, "producer_cells"),
+    (CellState::Mover, "mover_cells"),
+    (CellState::Killer, "killer_cells"),
+    (CellState::Armor, "armor_cells"),
+    (CellState::Eye, "eye_cells"),
+    (CellState::Heal, "heal_cells"),
+];
+
+fn print_header() {
+    let mut header = String::from("step,organism_count,alive_count,food_cells,wall_cells,avg_food_collected,avg_lifetime");
+    for (_, name) in CELL_COLUMNS.iter() {
+        header.push(',');
+        header.push_str(name);
+    }
+    println!("{}", header);
+}
+
+fn print_row(step: u32, stats: &GridStats) {
+    let mut row = format!("{},{},{},{},{},{:.3},{:.3}",
+        step, stats.organism_count, stats.alive_count, stats.food_cells,
+        stats.wall_cells, stats.avg_food_collected, stats.avg_lifetime);
+    for (state, _) in CELL_COLUMNS.iter() {
+        row.push_str(&format!(",{}", stats.organism_cell_count(*state)));
+    }
+    println!("{}", row);
+}
+
+fn main() {
+    let args = Args::parse().unwrap_or_else(|err| {
+        eprintln!("error: {}\n{}", err, USAGE);
+        process::exit(2);
+    });
+    
+    // Seeded grid so the same arguments always produce the same output
+    let mut grid = Grid::new_seeded(args.width, args.height, args.seed).unwrap_or_else(|err| {
+        eprintln!("error: {}", err);
+        process::exit(2);
+    });
+    if let Some(prob) = args.food_production_prob {
+        grid.food_production_prob = prob;
+    }
+    if let Some(max) = args.max_organisms {
+        grid.max_organisms = max;
+    }
+    grid.origin_of_life();
+    
+    print_header();
+    print_row(0, &grid.stats());
+    for step in 1..=args.steps {
+        grid.step();
+        if step % args.every == 0 || step == args.steps {
+            print_row(step, &grid.stats());
+        }
+    }
+}
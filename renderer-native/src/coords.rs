@@ -0,0 +1,49 @@
+// renderer-native/src/coords.rs
+
+/// Maps a window position to the grid cell under it, clamped to the grid.
+pub fn screen_to_grid(px: f64, py: f64, window_size: (u32, u32), grid_size: (u32, u32)) -> (u32, u32) {
+    let to_cell = |p: f64, window: u32, grid: u32| -> u32 {
+        if window == 0 || grid == 0 {
+            return 0;
+        }
+        let cell = (p * grid as f64 / window as f64).floor();
+        cell.clamp(0.0, (grid - 1) as f64) as u32
+    };
+    (
+        to_cell(px, window_size.0, grid_size.0),
+        to_cell(py, window_size.1, grid_size.1),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_window_maps_onto_cells() {
+        // 800x800 window over a 100x100 grid: 8 window pixels per cell
+        assert_eq!(screen_to_grid(0.0, 0.0, (800, 800), (100, 100)), (0, 0));
+        assert_eq!(screen_to_grid(7.9, 8.0, (800, 800), (100, 100)), (0, 1));
+        assert_eq!(screen_to_grid(404.0, 123.0, (800, 800), (100, 100)), (50, 15));
+        // Unequal scales per axis
+        assert_eq!(screen_to_grid(30.0, 30.0, (60, 120), (6, 6)), (3, 1));
+    }
+
+    #[test]
+    fn right_and_bottom_edges_clamp_to_the_last_cell() {
+        assert_eq!(screen_to_grid(800.0, 800.0, (800, 800), (100, 100)), (99, 99));
+        assert_eq!(screen_to_grid(5000.0, 10.0, (800, 800), (100, 100)), (99, 1));
+    }
+
+    #[test]
+    fn negative_positions_clamp_to_the_first_cell() {
+        assert_eq!(screen_to_grid(-1.0, -300.0, (800, 800), (100, 100)), (0, 0));
+        assert_eq!(screen_to_grid(-0.5, 16.0, (800, 800), (100, 100)), (0, 2));
+    }
+
+    #[test]
+    fn empty_window_or_grid_gives_the_origin() {
+        assert_eq!(screen_to_grid(10.0, 10.0, (0, 0), (100, 100)), (0, 0));
+        assert_eq!(screen_to_grid(10.0, 10.0, (800, 800), (0, 0)), (0, 0));
+    }
+}
@@ -0,0 +1,47 @@
+// renderer-native/src/frame.rs
+
+use simulation::Grid;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Split a 0xRRGGBB pixel into its red, green and blue bytes
+pub fn rgb(color: u32) -> [u8; 3] {
+    [(color >> 16) as u8, (color >> 8) as u8, color as u8]
+}
+
+/// Write the grid's pixels as a binary PPM (P6) image, one image pixel per cell
+pub fn write_ppm(w: &mut impl Write, grid: &Grid) -> io::Result<()> {
+    write!(w, "P6\n{} {}\n255\n", grid.width, grid.height)?;
+    for &color in &grid.pixels {
+        w.write_all(&rgb(color))?;
+    }
+    Ok(())
+}
+
+/// Save the current frame to `path` as a PPM image
+pub fn save_frame(path: impl AsRef<Path>, grid: &Grid) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    write_ppm(&mut file, grid)?;
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ppm_has_header_then_rgb_bytes_row_by_row() {
+        let mut grid = Grid::new_seeded(2, 2, 1).unwrap();
+        grid.pixels = vec![0x102030, 0x405060, 0x708090, 0xa0b0c0];
+        let mut out = Vec::new();
+        write_ppm(&mut out, &grid).unwrap();
+
+        let header = b"P6\n2 2\n255\n";
+        assert_eq!(&out[..header.len()], header);
+        assert_eq!(&out[header.len()..], &[
+            0x10, 0x20, 0x30, 0x40, 0x50, 0x60,
+            0x70, 0x80, 0x90, 0xa0, 0xb0, 0xc0,
+        ]);
+    }
+}
@@ -1,17 +1,98 @@
 // renderer-native/src/main.rs
 
+mod coords;
+mod frame;
+mod timestep;
+
 use pixels::{Error, Pixels, SurfaceTexture};
-use simulation::{Grid, CellStates};
+use std::process;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use coords::screen_to_grid;
+use frame::{rgb, save_frame};
+use timestep::FixedTimestep;
+use simulation::{Grid, CellState};
 use winit::{
     dpi::LogicalSize,
-    event::{Event, WindowEvent},
+    event::{ElementState, Event, KeyboardInput, MouseButton, VirtualKeyCode, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     window::WindowBuilder,
 };
 
+/// Simulation steps per second unless `--sps` says otherwise
+const DEFAULT_SPS: f64 = 60.0;
+/// Most steps run for a single frame, however far behind the simulation is
+const MAX_STEPS_PER_FRAME: u32 = 10;
+
+const USAGE: &str = "Usage: renderer-native [--width N] [--height N] [--seed N] [--pixel-size N] [--sps N]";
+
+/// Command line settings for the window and simulation
+struct Args {
+    width: u32,
+    height: u32,
+    seed: Option<u64>,          // Entropy-seeded when not given
+    pixel_size: u32,            // Window pixels per grid cell
+    sps: f64,                   // Simulation steps per second
+}
+
+impl Args {
+    fn parse() -> Result<Args, String> {
+        let mut args = Args {
+            width: 100,
+            height: 100,
+            seed: None,
+            pixel_size: 8,
+            sps: DEFAULT_SPS,
+        };
+        
+        let mut iter = std::env::args().skip(1);
+        while let Some(flag) = iter.next() {
+            if flag == "--help" || flag == "-h" {
+                println!("{}", USAGE);
+                process::exit(0);
+            }
+            let value = iter.next().ok_or_else(|| format!("missing value for {}", flag))?;
+            match flag.as_str() {
+                "--width" => args.width = parse_value(&flag, &value)?,
+                "--height" => args.height = parse_value(&flag, &value)?,
+                "--seed" => args.seed = Some(parse_value(&flag, &value)?),
+                "--pixel-size" => args.pixel_size = parse_value(&flag, &value)?,
+                "--sps" => args.sps = parse_value(&flag, &value)?,
+                _ => return Err(format!("unknown argument {}", flag)),
+            }
+        }
+        
+        if args.width == 0 || args.height == 0 {
+            return Err("width and height must be at least 1".to_string());
+        }
+        if args.pixel_size == 0 {
+            return Err("pixel size must be at least 1".to_string());
+        }
+        if args.sps.is_nan() || args.sps <= 0.0 {
+            return Err("--sps needs a positive number".to_string());
+        }
+        Ok(args)
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value '{}' for {}", value, flag))
+}
+
 fn main() -> Result<(), Error> {
-    // Initialize the simulation grid.
-    let mut grid = Grid::new(100, 100);
+    let args = Args::parse().unwrap_or_else(|err| {
+        eprintln!("error: {}\n{}", err, USAGE);
+        process::exit(2);
+    });
+    
+    // Initialize the simulation grid, seeded if asked so runs can be repeated
+    let grid = match args.seed {
+        Some(seed) => Grid::new_seeded(args.width, args.height, seed),
+        None => Grid::new(args.width, args.height),
+    };
+    let mut grid = grid.unwrap_or_else(|err| {
+        eprintln!("error: {}\n{}", err, USAGE);
+        process::exit(2);
+    });
     
     // Create the origin of life
     grid.origin_of_life();
@@ -20,7 +101,10 @@ fn main() -> Result<(), Error> {
     let event_loop = EventLoop::new();
     let window = WindowBuilder::new()
         .with_title("LifeEngine - Native")
-        .with_inner_size(LogicalSize::new(800.0, 800.0))
+        .with_inner_size(LogicalSize::new(
+            args.width as f64 * args.pixel_size as f64,
+            args.height as f64 * args.pixel_size as f64,
+        ))
         .build(&event_loop)
         .unwrap();
 
@@ -35,13 +119,37 @@ fn main() -> Result<(), Error> {
     grid.max_organisms = 1000;
     grid.lifespan_multiplier = 100;
     grid.insta_kill = false;
+    
+    // Mouse painting state
+    let mut cursor = (0.0, 0.0);     // Last cursor position in window pixels
+    let mut painting = false;       // Left button held down
+    let mut brush = CellState::Wall; // Cell type painted with the left button
+    
+    // Playback state
+    let mut paused = false;         // Space toggles
+    let mut step_once = false;      // Right arrow advances one step while paused
+    let mut timestep = FixedTimestep::new(args.sps, MAX_STEPS_PER_FRAME);
+    let mut last_frame = Instant::now();
 
     // Run the event loop.
     event_loop.run(move |event, _, control_flow| {
         match event {
             Event::RedrawRequested(_) => {
-                // Update the simulation
-                grid.step();
+                // Advance the simulation at a fixed rate unless paused;
+                // redraw either way so the screen stays current
+                let now = Instant::now();
+                let elapsed = now - last_frame;
+                last_frame = now;
+                if paused {
+                    if step_once {
+                        grid.step();
+                        step_once = false;
+                    }
+                } else {
+                    for _ in 0..timestep.advance(elapsed) {
+                        grid.step();
+                    }
+                }
                 
                 // Draw the grid
                 draw_grid(pixels.get_frame(), &grid);
@@ -54,24 +162,82 @@ fn main() -> Result<(), Error> {
             Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
                 *control_flow = ControlFlow::Exit;
             }
+            Event::WindowEvent { event: WindowEvent::CursorMoved { position, .. }, .. } => {
+                cursor = (position.x, position.y);
+                if painting {
+                    paint(&mut grid, cursor, window.inner_size().into(), brush);
+                }
+            }
+            Event::WindowEvent { event: WindowEvent::MouseInput { state, button, .. }, .. } => {
+                match (button, state) {
+                    (MouseButton::Left, ElementState::Pressed) => {
+                        painting = true;
+                        paint(&mut grid, cursor, window.inner_size().into(), brush);
+                    }
+                    (MouseButton::Left, ElementState::Released) => painting = false,
+                    (MouseButton::Right, ElementState::Pressed) => {
+                        let (x, y) = screen_to_grid(cursor.0, cursor.1, window.inner_size().into(), (grid.width, grid.height));
+                        grid.create_basic_organism(x, y);
+                    }
+                    _ => {}
+                }
+            }
+            Event::WindowEvent {
+                event: WindowEvent::KeyboardInput {
+                    input: KeyboardInput { virtual_keycode: Some(key), state: ElementState::Pressed, .. },
+                    ..
+                },
+                ..
+            } => {
+                match key {
+                    // Number keys pick the brush
+                    VirtualKeyCode::Key1 => brush = CellState::Wall,
+                    VirtualKeyCode::Key2 => brush = CellState::Food,
+                    VirtualKeyCode::Key3 => brush = CellState::Empty,
+                    VirtualKeyCode::Key4 => brush = CellState::Hazard,
+                    // Playback controls
+                    VirtualKeyCode::Space => paused = !paused,
+                    VirtualKeyCode::Right if paused => step_once = true,
+                    VirtualKeyCode::R => grid.reset(false),
+                    VirtualKeyCode::P => screenshot(&grid),
+                    VirtualKeyCode::E => {
+                        grid.show_eye_direction = !grid.show_eye_direction;
+                        grid.refresh_pixels();
+                    }
+                    _ => {}
+                }
+            }
             _ => {}
         }
         window.request_redraw();
     });
 }
 
+/// Saves the current frame to a timestamped PPM in the working directory
+fn screenshot(grid: &Grid) {
+    let millis = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_millis());
+    let path = format!("frame-{}.ppm", millis);
+    match save_frame(&path, grid) {
+        Ok(()) => println!("saved {}", path),
+        Err(err) => eprintln!("couldn't save {}: {}", path, err),
+    }
+}
+
+/// Paints the brush onto the cell under the cursor, leaving organisms alone.
+fn paint(grid: &mut Grid, cursor: (f64, f64), window_size: (u32, u32), brush: CellState) {
+    let (x, y) = screen_to_grid(cursor.0, cursor.1, window_size, (grid.width, grid.height));
+    if grid.get_cell(x, y).is_some_and(|cell| cell.owner.is_none()) {
+        grid.set_cell(x, y, brush, None);
+    }
+}
+
 /// Draws the simulation grid into the frame buffer.
 fn draw_grid(frame: &mut [u8], grid: &Grid) {
     // Each pixel is RGBA (4 bytes).
     for row in 0..grid.height {
         for col in 0..grid.width {
             let i = (row * grid.width + col) as usize * 4;
-            let cell_color = grid.get_pixel(col, row);
-            
-            // Extract RGB components
-            let r = ((cell_color >> 16) & 0xFF) as u8;
-            let g = ((cell_color >> 8) & 0xFF) as u8;
-            let b = (cell_color & 0xFF) as u8;
+            let [r, g, b] = rgb(grid.get_pixel(col, row));
             
             // Set RGBA values in the frame buffer
             frame[i] = r;
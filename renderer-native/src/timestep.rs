@@ -0,0 +1,72 @@
+// renderer-native/src/timestep.rs
+
+use std::time::Duration;
+
+/// Fixed-timestep accumulator: turns elapsed frame time into a number of simulation steps,
+/// so the simulation runs at a steady rate whatever the redraw rate is.
+pub struct FixedTimestep {
+    step_duration: Duration,   // Time covered by one simulation step
+    accumulator: Duration,     // Elapsed time not yet turned into steps
+    max_steps_per_frame: u32,  // Cap so a slow frame can't snowball into ever more steps
+}
+
+impl FixedTimestep {
+    pub fn new(steps_per_second: f64, max_steps_per_frame: u32) -> Self {
+        FixedTimestep {
+            step_duration: Duration::from_secs_f64(1.0 / steps_per_second.max(0.001)),
+            accumulator: Duration::ZERO,
+            max_steps_per_frame,
+        }
+    }
+
+    /// Add the time since the last frame and return how many steps to run now
+    pub fn advance(&mut self, elapsed: Duration) -> u32 {
+        self.accumulator += elapsed;
+        let mut steps = 0;
+        while self.accumulator >= self.step_duration && steps < self.max_steps_per_frame {
+            self.accumulator -= self.step_duration;
+            steps += 1;
+        }
+
+        // Hit the cap: drop the backlog instead of trying to catch up later
+        if steps == self.max_steps_per_frame {
+            self.accumulator = self.accumulator.min(self.step_duration);
+        }
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn whole_steps_are_taken_from_the_elapsed_time() {
+        let mut timestep = FixedTimestep::new(10.0, 100);
+        assert_eq!(timestep.advance(ms(0)), 0);
+        assert_eq!(timestep.advance(ms(100)), 1);
+        assert_eq!(timestep.advance(ms(300)), 3);
+    }
+
+    #[test]
+    fn leftover_time_carries_over_to_the_next_frame() {
+        let mut timestep = FixedTimestep::new(10.0, 100);
+        assert_eq!(timestep.advance(ms(250)), 2);
+        assert_eq!(timestep.advance(ms(40)), 0);
+        // 50 + 40 + 10 ms adds up to one more step
+        assert_eq!(timestep.advance(ms(10)), 1);
+    }
+
+    #[test]
+    fn steps_per_frame_are_capped_and_the_backlog_dropped() {
+        let mut timestep = FixedTimestep::new(100.0, 3);
+        assert_eq!(timestep.advance(ms(1000)), 3);
+        // At most one step's worth of the slow frame is kept
+        assert_eq!(timestep.advance(ms(0)), 1);
+        assert_eq!(timestep.advance(ms(0)), 0);
+    }
+}
@@ -2,12 +2,15 @@
 // At the top of renderer-wasm/src/lib.rs
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement};
-use simulation::{Grid as CoreGrid, CellStates, Organism};
+use wasm_bindgen::Clamped;
+use web_sys::{window, CanvasRenderingContext2d, HtmlCanvasElement, ImageData};
+use simulation::{Grid as CoreGrid, CellState, Direction, Organism, MutationParams, Palette, RenderMode};
 use std::cell::RefCell;
 use std::rc::Rc;
+use viewport::Viewport;
 
 mod utils;
+mod viewport;
 
 // Called when the wasm module is instantiated
 #[wasm_bindgen(start)]
@@ -16,6 +19,10 @@ pub fn start() -> Result<(), JsValue> {
     Ok(())
 }
 
+/// Numbers per organism in `WasmGrid::organisms_flat`:
+/// id, x, y, cell count, food collected, lifetime, health and alive (1 or 0)
+pub const ORG_STRIDE: usize = 8;
+
 #[wasm_bindgen]
 pub struct WasmGrid {
     inner: CoreGrid,
@@ -24,16 +31,29 @@ pub struct WasmGrid {
 #[wasm_bindgen]
 impl WasmGrid {
     /// Creates a new WasmGrid with the given dimensions.
+    /// Throws if a side is zero or the grid is too large.
     #[wasm_bindgen(constructor)]
-    pub fn new(width: u32, height: u32) -> WasmGrid {
-        WasmGrid {
-            inner: CoreGrid::new(width, height),
-        }
+    pub fn new(width: u32, height: u32) -> Result<WasmGrid, JsValue> {
+        let inner = CoreGrid::new(width, height).map_err(|err| js_sys::Error::new(&err.to_string()))?;
+        Ok(WasmGrid { inner })
+    }
+    /// Creates a new WasmGrid whose simulation is reproducible from `seed`.
+    /// Throws on the same sizes as the constructor.
+    pub fn new_seeded(width: u32, height: u32, seed: u64) -> Result<WasmGrid, JsValue> {
+        let inner = CoreGrid::new_seeded(width, height, seed).map_err(|err| js_sys::Error::new(&err.to_string()))?;
+        Ok(WasmGrid { inner })
+    }
+    
+    /// Restart the simulation RNG from a fixed seed.
+    pub fn reseed(&mut self, seed: u64) {
+        self.inner.reseed(seed);
     }
-    // Add this method
+    
+    /// Set whether food cells block offspring from being placed on them
     pub fn set_food_blocks_reproduction(&mut self, blocks: bool) {
-        self.inner.food_blocks_reproduction = blocks;
+        self.inner.set_food_blocks_reproduction(blocks);
     }
+    
     /// Sets a pixel in the grid.
     pub fn set_pixel(&mut self, x: u32, y: u32, color: u32) {
         self.inner.set_pixel(x, y, color);
@@ -41,21 +61,17 @@ impl WasmGrid {
     pub fn set_movers_can_produce(&mut self, can_produce: bool) {
         self.inner.movers_can_produce = can_produce;
     }
-    /// Sets a cell with a specific state
-    pub fn set_cell(&mut self, x: u32, y: u32, state_idx: u8) {
-        let state = match state_idx {
-            0 => CellStates::Empty,
-            1 => CellStates::Food,
-            2 => CellStates::Wall,
-            3 => CellStates::Mouth,
-            4 => CellStates::Producer,
-            5 => CellStates::Mover,
-            6 => CellStates::Killer,
-            7 => CellStates::Armor,
-            8 => CellStates::Eye,
-            _ => CellStates::Empty,
-        };
-        self.inner.set_cell(x, y, state, None);
+    /// Sets a cell with a specific state, leaving cells of living organisms alone.
+    /// Returns whether the cell was set.
+    pub fn set_cell(&mut self, x: u32, y: u32, state_idx: u8) -> bool {
+        let state = cell_state(state_idx).unwrap_or(CellState::Empty);
+        self.inner.set_cell_checked(x, y, state, None)
+    }
+
+    /// Resize the grid, keeping the top-left overlap and removing organisms that no longer fit.
+    /// Throws, leaving the grid unchanged, if a side is zero or the grid is too large.
+    pub fn resize(&mut self, width: u32, height: u32) -> Result<(), JsValue> {
+        self.inner.resize(width, height).map_err(|err| js_sys::Error::new(&err.to_string()).into())
     }
 
     /// Returns the grid width.
@@ -73,21 +89,279 @@ impl WasmGrid {
         self.inner.get_pixel(x, y)
     }
     
+    /// Pointer to the pixel buffer in WASM memory, for wrapping in a `Uint32Array`
+    /// of `pixels_len()` elements, row by row. Each pixel is a u32 packed `0xRRGGBB`:
+    /// red in bits 16-23, green in 8-15 and blue in 0-7, so JS has to expand it to
+    /// RGBA bytes before handing it to `ImageData`. The pointer is only valid until the
+    /// grid is next stepped, resized or reset.
+    pub fn pixels_ptr(&self) -> *const u32 {
+        self.inner.pixels.as_ptr()
+    }
+    
+    /// Number of pixels behind `pixels_ptr()` (always `width * height`)
+    pub fn pixels_len(&self) -> usize {
+        self.inner.pixels.len()
+    }
+    
+    /// Cells whose state changed since the last call, flattened as `[x, y, color, ...]`
+    /// with colors packed `0xRRGGBB`, so only those cells need repainting
+    pub fn take_changes(&mut self) -> Vec<u32> {
+        let changes = self.inner.take_changes();
+        let mut flat = Vec::with_capacity(changes.len() * 3);
+        for (x, y, _) in changes {
+            flat.extend_from_slice(&[x, y, self.inner.get_pixel(x, y)]);
+        }
+        flat
+    }
+    
     /// Update the grid simulation.
     pub fn step(&mut self) {
         self.inner.step();
     }
     
+    /// Run `n` steps, repainting only after the last; faster when rendering every few steps.
+    pub fn step_n(&mut self, n: u32) {
+        self.inner.step_n(n);
+    }
+    
     /// Reset the grid
     pub fn reset(&mut self, clear_walls: bool) {
         self.inner.reset(clear_walls);
     }
     
+    /// Remove all food, keeping organisms and walls
+    pub fn clear_food(&mut self) {
+        self.inner.clear_food();
+    }
+    
+    /// Remove all organisms, keeping walls and food
+    pub fn clear_organisms(&mut self) {
+        self.inner.clear_organisms();
+    }
+    
     /// Get the number of organisms
     pub fn organism_count(&self) -> usize {
         self.inner.organisms.len()
     }
     
+    // Per-organism getters for click-to-inspect. `i` indexes the organism list
+    // (0..organism_count()); out of range gives undefined.
+    
+    /// X position of the i-th organism
+    pub fn organism_x(&self, i: usize) -> Option<u32> {
+        self.inner.organisms.get(i).map(|org| org.x)
+    }
+    
+    /// Y position of the i-th organism
+    pub fn organism_y(&self, i: usize) -> Option<u32> {
+        self.inner.organisms.get(i).map(|org| org.y)
+    }
+    
+    /// Number of cells in the i-th organism
+    pub fn organism_cell_count(&self, i: usize) -> Option<usize> {
+        self.inner.organisms.get(i).map(|org| org.cells.len())
+    }
+    
+    /// Food collected and not yet spent by the i-th organism
+    pub fn organism_food(&self, i: usize) -> Option<u32> {
+        self.inner.organisms.get(i).map(|org| org.food_collected)
+    }
+    
+    /// Steps the i-th organism has lived
+    pub fn organism_lifetime(&self, i: usize) -> Option<u32> {
+        self.inner.organisms.get(i).map(|org| org.lifetime)
+    }
+    
+    /// Whether the i-th organism is alive
+    pub fn organism_is_alive(&self, i: usize) -> Option<bool> {
+        self.inner.organisms.get(i).map(|org| org.is_alive)
+    }
+    
+    /// Every organism packed into one Float32Array, `org_stride()` numbers each, in the
+    /// same order as the per-index getters (see `ORG_STRIDE` for the fields)
+    pub fn organisms_flat(&self) -> Vec<f32> {
+        let mut flat = Vec::with_capacity(self.inner.organisms.len() * ORG_STRIDE);
+        for org in &self.inner.organisms {
+            flat.extend_from_slice(&[
+                org.id as f32,
+                org.x as f32,
+                org.y as f32,
+                org.cells.len() as f32,
+                org.food_collected as f32,
+                org.lifetime as f32,
+                org.health as f32,
+                if org.is_alive { 1.0 } else { 0.0 },
+            ]);
+        }
+        flat
+    }
+    
+    /// Numbers per organism in `organisms_flat`
+    pub fn org_stride() -> usize {
+        ORG_STRIDE
+    }
+    
+    /// Number of steps run since the grid was created or last reset
+    pub fn tick_count(&self) -> u64 {
+        self.inner.tick_count()
+    }
+    
+    /// Get the number of living organisms
+    pub fn alive_count(&self) -> usize {
+        self.inner.stats().alive_count
+    }
+    
+    /// Get the number of food cells on the grid
+    pub fn food_cell_count(&self) -> usize {
+        self.inner.stats().food_cells
+    }
+    
+    /// Get the number of wall cells on the grid
+    pub fn wall_cell_count(&self) -> usize {
+        self.inner.stats().wall_cells
+    }
+    
+    /// Get the number of living mouth cells
+    pub fn mouth_cell_count(&self) -> usize {
+        self.inner.stats().organism_cell_count(CellState::Mouth)
+    }
+    
+    /// Get the number of living producer cells
+    pub fn producer_cell_count(&self) -> usize {
+        self.inner.stats().organism_cell_count(CellState::Producer)
+    }
+    
+    /// Get the number of living mover cells
+    pub fn mover_cell_count(&self) -> usize {
+        self.inner.stats().organism_cell_count(CellState::Mover)
+    }
+    
+    /// Get the number of living killer cells
+    pub fn killer_cell_count(&self) -> usize {
+        self.inner.stats().organism_cell_count(CellState::Killer)
+    }
+    
+    /// Get the number of living armor cells
+    pub fn armor_cell_count(&self) -> usize {
+        self.inner.stats().organism_cell_count(CellState::Armor)
+    }
+    
+    /// Get the number of living eye cells
+    pub fn eye_cell_count(&self) -> usize {
+        self.inner.stats().organism_cell_count(CellState::Eye)
+    }
+    
+    /// Get the number of living heal cells
+    pub fn heal_cell_count(&self) -> usize {
+        self.inner.stats().organism_cell_count(CellState::Heal)
+    }
+    
+    /// Get the number of hazard cells on the grid
+    pub fn hazard_cell_count(&self) -> usize {
+        self.inner.stats().hazard_cells
+    }
+    
+    /// Get the counts for the most recent step as a plain JS object
+    pub fn sim_stats(&self) -> JsValue {
+        let stats = self.inner.sim_stats();
+        js_object(&[
+            ("step", stats.step as f64),
+            ("organisms", stats.organisms as f64),
+            ("births", stats.births as f64),
+            ("deathsOldAge", stats.deaths_old_age as f64),
+            ("deathsKilled", stats.deaths_killed as f64),
+            ("deathsStarved", stats.deaths_starved as f64),
+            ("foodCells", stats.food_cells as f64),
+            ("avgOrganismSize", stats.avg_organism_size as f64),
+        ])
+    }
+    
+    /// Deaths by cause since the grid was created or last reset, as a plain JS object
+    /// with oldAge, killed and starved
+    pub fn death_histogram(&self) -> JsValue {
+        let deaths = self.inner.death_histogram();
+        js_object(&[
+            ("oldAge", deaths.old_age as f64),
+            ("killed", deaths.killed as f64),
+            ("starved", deaths.starved as f64),
+        ])
+    }
+    
+    /// Summary of the organism occupying (x, y) as a plain JS object with id, cellCount,
+    /// foodCollected, lifetime and mutability, or null if no organism is there
+    pub fn organism_at(&self, x: u32, y: u32) -> JsValue {
+        match self.inner.organism_at(x, y) {
+            Some(org) => js_object(&[
+                ("id", org.id as f64),
+                ("cellCount", org.cells.len() as f64),
+                ("foodCollected", org.food_collected as f64),
+                ("lifetime", org.lifetime as f64),
+                ("mutability", org.mutability as f64),
+            ]),
+            None => JsValue::NULL,
+        }
+    }
+    
+    /// Summary of the living organism with the most food eaten plus kills, as a plain
+    /// JS object with id, cellCount, kills, totalFoodEaten and lifetime, or null if none
+    pub fn most_successful_organism(&self) -> JsValue {
+        match self.inner.most_successful_organism() {
+            Some(org) => js_object(&[
+                ("id", org.id as f64),
+                ("cellCount", org.cells.len() as f64),
+                ("kills", org.kills as f64),
+                ("totalFoodEaten", org.total_food_eaten as f64),
+                ("lifetime", org.lifetime as f64),
+            ]),
+            None => JsValue::NULL,
+        }
+    }
+    
+    /// Set how many steps of statistics history to keep
+    pub fn set_stats_history_len(&mut self, len: usize) {
+        self.inner.stats_history_len = len;
+    }
+    
+    /// Get the average food collected by living organisms
+    pub fn avg_food_collected(&self) -> f32 {
+        self.inner.stats().avg_food_collected
+    }
+    
+    /// Get the average age of living organisms, in steps
+    pub fn avg_lifetime(&self) -> f32 {
+        self.inner.stats().avg_lifetime
+    }
+    
+    /// Draw a wall along the line from (x0, y0) to (x1, y1)
+    pub fn place_wall_line(&mut self, x0: u32, y0: u32, x1: u32, y1: u32) {
+        self.inner.place_wall_line(x0, y0, x1, y1);
+    }
+    
+    /// Wall in the border of a rectangle, leaving the inside alone
+    pub fn place_wall_rect(&mut self, x: u32, y: u32, w: u32, h: u32) {
+        self.inner.place_wall_rect(x, y, w, h);
+    }
+    
+    /// Draw a rectangle of wall, solid or just the border
+    pub fn draw_wall_rect(&mut self, x: u32, y: u32, w: u32, h: u32, filled: bool) {
+        self.inner.draw_wall_rect(x, y, w, h, filled);
+    }
+    
+    /// Draw a rectangle of hazard, solid or just the border
+    pub fn draw_hazard_rect(&mut self, x: u32, y: u32, w: u32, h: u32, filled: bool) {
+        self.inner.draw_hazard_rect(x, y, w, h, filled);
+    }
+    
+    /// Wall off the edge of the grid
+    pub fn add_border_walls(&mut self) {
+        self.inner.add_border_walls();
+    }
+    
+    /// Remove the wall at (x, y), if there is one
+    pub fn clear_wall(&mut self, x: u32, y: u32) {
+        self.inner.clear_wall(x, y);
+    }
+    
     /// Set the food production probability for producer cells
     pub fn set_food_production_rate(&mut self, rate: f32) {
         self.inner.food_production_prob = rate;
@@ -95,7 +369,7 @@ impl WasmGrid {
     
     /// Set the random food drop probability
     pub fn set_food_drop_rate(&mut self, rate: f32) {
-        self.inner.food_drop_prob = rate;
+        self.inner.set_food_drop_rate(rate);
     }
     
     /// Set the maximum number of organisms
@@ -113,6 +387,237 @@ impl WasmGrid {
         self.inner.insta_kill = insta_kill;
     }
     
+    /// Set the steps between each heal cell restoring 1 health (0 turns healing off)
+    pub fn set_heal_interval(&mut self, interval: u32) {
+        self.inner.heal_interval = interval;
+    }
+    
+    /// Set the damage each killer cell deals per hit
+    pub fn set_killer_damage(&mut self, damage: u32) {
+        self.inner.killer_damage = damage;
+    }
+    
+    /// Set how much damage an armor cell takes off each hit
+    pub fn set_armor_reduction(&mut self, reduction: u32) {
+        self.inner.armor_reduction = reduction;
+    }
+    
+    /// Set whether cells eat, kill and produce into diagonal neighbours too
+    pub fn set_use_diagonal_neighbors(&mut self, enabled: bool) {
+        self.inner.use_diagonal_neighbors = enabled;
+    }
+    
+    /// Set whether armor also shields the cells beside it from killers
+    pub fn set_armor_blocks_adjacent(&mut self, blocks: bool) {
+        self.inner.armor_blocks_adjacent = blocks;
+    }
+    
+    /// Set how far eyeless movers smell food and linger near it (0 turns smell off)
+    pub fn set_sense_radius(&mut self, radius: u32) {
+        self.inner.sense_radius = radius;
+    }
+    
+    /// Set whether eyeless movers turn toward the richest nearby food
+    pub fn set_food_gradient_movement(&mut self, enabled: bool) {
+        self.inner.food_gradient_movement = enabled;
+    }
+    
+    /// Give newly placed organisms a random, heritable reaction to what their eyes see
+    pub fn set_brains_enabled(&mut self, enabled: bool) {
+        self.inner.brains_enabled = enabled;
+    }
+    
+    /// Set the chance (0-100) that an offspring re-rolls one reaction of its inherited brain
+    pub fn set_brain_mutate_prob(&mut self, prob: f32) {
+        let mut params = self.inner.mutation_params;
+        params.brain_mutate_prob = prob;
+        self.inner.set_mutation_params(params);
+    }
+    
+    /// Set the largest body mutation can grow to (0 means unlimited)
+    pub fn set_max_organism_cells(&mut self, max_cells: usize) {
+        self.inner.max_organism_cells = max_cells;
+    }
+    
+    /// Set the food a mover needs to reproduce on top of one per cell
+    pub fn set_extra_mover_food_cost(&mut self, cost: u32) {
+        self.inner.extra_mover_food_cost = cost;
+    }
+    
+    /// Scale the food needed to reproduce (2.0 doubles it)
+    pub fn set_reproduction_cost_multiplier(&mut self, multiplier: f32) {
+        self.inner.reproduction_cost_multiplier = multiplier;
+    }
+    
+    /// Start throttling births once the population passes this share of the cap (1.0 = off)
+    pub fn set_soft_cap_fraction(&mut self, fraction: f32) {
+        self.inner.soft_cap_fraction = fraction;
+    }
+    
+    /// Place offspring in a fixed order instead of a random direction
+    pub fn set_deterministic_placement(&mut self, deterministic: bool) {
+        self.inner.deterministic_placement = deterministic;
+    }
+    
+    /// Set how many steps an organism waits between births (0 = no wait)
+    pub fn set_reproduction_cooldown(&mut self, steps: u32) {
+        self.inner.reproduction_cooldown = steps;
+    }
+    
+    /// Set how many tiles eye cells can see
+    pub fn set_eye_view_distance(&mut self, distance: u32) {
+        self.inner.eye_view_distance = distance;
+    }
+    
+    /// Set whether organisms wrap around the grid edges
+    pub fn set_wrap_edges(&mut self, wrap: bool) {
+        self.inner.wrap_edges = wrap;
+    }
+    
+    /// Set the energy a producer organism spends per food it makes
+    pub fn set_producer_energy_cost(&mut self, cost: u32) {
+        self.inner.producer_energy_cost = cost;
+    }
+    
+    /// Set the energy a mover organism spends per step it moves
+    pub fn set_move_energy_cost(&mut self, cost: u32) {
+        self.inner.move_energy_cost = cost;
+    }
+    
+    /// Set the collected food a mover spends per step it moves (fractions add up over moves)
+    pub fn set_move_food_cost(&mut self, cost: f32) {
+        self.inner.move_food_cost = cost;
+    }
+    
+    /// Set how many steps a mover can go unable to pay for a move before it starves
+    pub fn set_starvation_steps(&mut self, steps: u32) {
+        self.inner.starvation_steps = steps;
+    }
+    
+    /// Set the energy every organism regains per step
+    pub fn set_energy_regen(&mut self, regen: u32) {
+        self.inner.energy_regen = regen;
+    }
+    
+    /// Switch metabolism on or off: energy then comes only from food and empty organisms starve
+    pub fn set_metabolism_enabled(&mut self, enabled: bool) {
+        self.inner.metabolism_enabled = enabled;
+    }
+    
+    /// Set the energy each cell costs per step under metabolism
+    pub fn set_cell_upkeep(&mut self, upkeep: u32) {
+        self.inner.cell_upkeep = upkeep;
+    }
+    
+    /// Set the energy gained per food eaten under metabolism
+    pub fn set_food_energy(&mut self, energy: u32) {
+        self.inner.food_energy = energy;
+    }
+    
+    /// Tint organisms with a species color while keeping cell types visible
+    pub fn set_color_by_owner(&mut self, enabled: bool) {
+        self.inner.color_by_owner = enabled;
+        self.inner.refresh_pixels();
+    }
+    
+    /// Recolor cells with eleven 0xRRGGBB colors for Empty, Food, Wall, Mouth, Producer,
+    /// Mover, Killer, Armor, Eye, Heal and Hazard. Returns false, changing nothing, for any other count.
+    pub fn set_palette(&mut self, colors: &[u32]) -> bool {
+        match <[u32; 11]>::try_from(colors) {
+            Ok(colors) => {
+                self.inner.set_palette(Palette::new(colors));
+                true
+            }
+            Err(_) => false,
+        }
+    }
+    
+    /// Choose what pixels show: 0 normal, 1 food heatmap, 2 owner map, 3 age map.
+    /// Returns false, changing nothing, for any other value.
+    pub fn set_render_mode(&mut self, mode: u8) -> bool {
+        let mode = match mode {
+            0 => RenderMode::Normal,
+            1 => RenderMode::FoodHeatmap,
+            2 => RenderMode::OwnerMap,
+            3 => RenderMode::AgeMap,
+            _ => return false,
+        };
+        self.inner.set_render_mode(mode);
+        true
+    }
+    
+    /// Set how many steps uneaten food lasts before spoiling (0 keeps food forever)
+    pub fn set_food_lifespan(&mut self, steps: u32) {
+        self.inner.food_lifespan = if steps == 0 { None } else { Some(steps) };
+    }
+    
+    /// Visit only food cells when spoiling and counting food; faster on large sparse grids
+    pub fn set_sparse_food_scan(&mut self, enabled: bool) {
+        self.inner.sparse_food_scan = enabled;
+    }
+    
+    /// Tint eye cells by the way they look; renderers zoomed in far enough also draw a tick
+    pub fn set_show_eye_direction(&mut self, enabled: bool) {
+        self.inner.show_eye_direction = enabled;
+        self.inner.refresh_pixels();
+    }
+    
+    /// Every living eye packed into one Uint32Array as x, y and direction triples,
+    /// direction being 0-3 clockwise from up
+    pub fn eye_overlays_flat(&self) -> Vec<u32> {
+        self.inner.eye_overlays().into_iter()
+            .flat_map(|(x, y, direction)| [x, y, direction as u32])
+            .collect()
+    }
+    
+    /// Color organisms by species (anatomy) instead of by cell type
+    pub fn set_color_by_species(&mut self, enabled: bool) {
+        self.inner.color_by_species = enabled;
+        self.inner.refresh_pixels();
+    }
+    
+    /// Set the percent chances (0-100) to add, change and remove a cell when offspring mutate
+    pub fn set_mutation_probs(&mut self, add_prob: f32, change_prob: f32, remove_prob: f32) {
+        let mut params = self.inner.mutation_params;
+        params.add_prob = add_prob;
+        params.change_prob = change_prob;
+        params.remove_prob = remove_prob;
+        self.inner.set_mutation_params(params);
+    }
+    
+    /// Set every mutation chance (0-100) at once, including the move range and mutability nudges
+    pub fn set_mutation_config(&mut self, add_prob: f32, change_prob: f32, remove_prob: f32,
+                               move_range_mutate_prob: f32, mutability_mutate_prob: f32) {
+        self.inner.set_mutation_params(MutationParams {
+            add_prob,
+            change_prob,
+            remove_prob,
+            move_range_mutate_prob,
+            mutability_mutate_prob,
+            brain_mutate_prob: self.inner.mutation_params.brain_mutate_prob,
+        });
+    }
+    
+    /// Light the grid from `top` at the first row to `bottom` at the last (0.0-1.0)
+    pub fn set_light_gradient(&mut self, top: f32, bottom: f32) {
+        self.inner.set_light_gradient(top, bottom);
+    }
+    
+    /// Light level (0.0-1.0) at (x, y), for tinting the background
+    pub fn light_at(&self, x: u32, y: u32) -> f32 {
+        self.inner.light_at(x, y)
+    }
+    
+    /// Energy fraction (0.0-1.0) of the organism at (x, y), or -1.0 if the cell is unowned
+    pub fn energy_fraction_at(&self, x: u32, y: u32) -> f32 {
+        self.inner.energy_fraction_at(x, y).unwrap_or(-1.0)
+    }
+    
+    /// Age fraction (0.0-1.0) of the organism at (x, y), or -1.0 if the cell is unowned
+    pub fn lifetime_fraction_at(&self, x: u32, y: u32) -> f32 {
+        self.inner.lifetime_fraction_at(x, y).unwrap_or(-1.0)
+    }
+    
     /// Add a simple organism at the specified position
     #[wasm_bindgen]
     pub fn add_organism(&mut self, x: u32, y: u32) -> bool {
@@ -122,43 +627,85 @@ impl WasmGrid {
     /// Add a custom organism
     #[wasm_bindgen]
     pub fn add_custom_organism(&mut self, x: u32, y: u32, organism_type: u8) -> bool {
+        // Organism::new already places the mouth at the center
         let mut organism = Organism::new(self.inner.next_organism_id, x, y);
         
         match organism_type {
             // Basic producer
             0 => {
-                organism.add_cell(CellStates::Mouth, 0, 0);
-                organism.add_cell(CellStates::Producer, 1, 0);
-                organism.add_cell(CellStates::Producer, -1, 0);
-                organism.add_cell(CellStates::Producer, 0, 1);
-                organism.add_cell(CellStates::Producer, 0, -1);
+                organism.add_cell(CellState::Producer, 1, 0);
+                organism.add_cell(CellState::Producer, -1, 0);
+                organism.add_cell(CellState::Producer, 0, 1);
+                organism.add_cell(CellState::Producer, 0, -1);
             },
             // Mobile hunter
             1 => {
-                organism.add_cell(CellStates::Mouth, 0, 0);
-                organism.add_cell(CellStates::Mover, 1, 0);
-                organism.add_cell(CellStates::Killer, 0, 1);
-                organism.add_cell(CellStates::Eye, -1, 0);
+                organism.add_cell(CellState::Mover, 1, 0);
+                organism.add_cell(CellState::Killer, 0, 1);
+                organism.add_cell(CellState::Eye, -1, 0);
             },
             // Armored producer
             2 => {
-                organism.add_cell(CellStates::Mouth, 0, 0);
-                organism.add_cell(CellStates::Producer, 1, 0);
-                organism.add_cell(CellStates::Producer, -1, 0);
-                organism.add_cell(CellStates::Armor, 0, 1);
-                organism.add_cell(CellStates::Armor, 0, -1);
+                organism.add_cell(CellState::Producer, 1, 0);
+                organism.add_cell(CellState::Producer, -1, 0);
+                organism.add_cell(CellState::Armor, 0, 1);
+                organism.add_cell(CellState::Armor, 0, -1);
             },
             // Default to basic producer
             _ => {
-                organism.add_cell(CellStates::Mouth, 0, 0);
-                organism.add_cell(CellStates::Producer, 1, 0);
-                organism.add_cell(CellStates::Producer, -1, 0);
+                organism.add_cell(CellState::Producer, 1, 0);
+                organism.add_cell(CellState::Producer, -1, 0);
             }
         }
+        organism.energy = organism.max_energy();
         
         self.inner.add_organism(organism)
     }
     
+    /// Place an organism built from a genome string (see `Organism::to_genome`) centered
+    /// on (x, y). Returns false if the genome doesn't parse, logging why to the console,
+    /// or if the organism doesn't fit there.
+    pub fn add_organism_from_genome(&mut self, x: u32, y: u32, genome: &str) -> bool {
+        let mut organism = match Organism::from_genome(genome) {
+            Ok(organism) => organism,
+            Err(err) => {
+                console_error!("invalid genome: {}", err);
+                return false;
+            }
+        };
+        organism.x = x;
+        organism.y = y;
+        self.inner.add_organism(organism)
+    }
+    
+    /// Set the layout new basic organisms get from parallel arrays of cell state indices
+    /// (as for `set_cell`) and x, y offsets. Empty arrays restore the built-in layout.
+    /// Returns false, logging why to the console, if the arrays differ in length or the
+    /// layout is invalid.
+    pub fn set_default_anatomy(&mut self, states: &[u8], xs: &[i32], ys: &[i32]) -> bool {
+        if states.len() != xs.len() || states.len() != ys.len() {
+            console_error!("anatomy arrays differ in length: {}, {} and {}", states.len(), xs.len(), ys.len());
+            return false;
+        }
+        let mut cells = Vec::with_capacity(states.len());
+        for ((&state_idx, &x), &y) in states.iter().zip(xs).zip(ys) {
+            match cell_state(state_idx) {
+                Some(state) => cells.push((state, x, y)),
+                None => {
+                    console_error!("invalid cell state {}", state_idx);
+                    return false;
+                }
+            }
+        }
+        match self.inner.set_default_anatomy(cells) {
+            Ok(()) => true,
+            Err(err) => {
+                console_error!("invalid anatomy: {}", err);
+                false
+            }
+        }
+    }
+    
     /// Create the "Origin of Life" organism in the center
     #[wasm_bindgen]
     pub fn origin_of_life(&mut self) {
@@ -171,6 +718,7 @@ pub struct Renderer {
     canvas: HtmlCanvasElement,
     context: CanvasRenderingContext2d,
     pixel_size: u32,
+    viewport: Option<Viewport>, // Part of the grid to draw, or None for all of it
 }
 
 #[wasm_bindgen]
@@ -196,44 +744,107 @@ impl Renderer {
             canvas,
             context,
             pixel_size,
+            viewport: None,
         }
     }
+    
+    /// Only draw the `w` x `h` cells starting at (x, y). Viewports reaching past the
+    /// grid are clamped to it when rendering.
+    pub fn set_viewport(&mut self, x: u32, y: u32, w: u32, h: u32) {
+        self.viewport = Some(Viewport { x, y, width: w, height: h });
+    }
+    
+    /// Go back to drawing the whole grid
+    pub fn clear_viewport(&mut self) {
+        self.viewport = None;
+    }
+    
+    /// Set how many canvas pixels each cell takes up, to zoom in or out
+    pub fn set_pixel_size(&mut self, pixel_size: u32) {
+        self.pixel_size = pixel_size.max(1);
+    }
 
-    /// Render the grid by drawing each pixel on the canvas.
+    /// Render the visible part of the grid by building an RGBA image in memory
+    /// and blitting it to the canvas with a single `putImageData` call.
     pub fn render(&self, grid: &WasmGrid) {
-        // Resize canvas based on grid dimensions
-        self.canvas.set_width(grid.width() * self.pixel_size);
-        self.canvas.set_height(grid.height() * self.pixel_size);
-
-        // Clear canvas
-        self.context.clear_rect(
-            0.0,
-            0.0,
-            self.canvas.width() as f64,
-            self.canvas.height() as f64,
-        );
-
-        // Draw each pixel from grid
-        for y in 0..grid.height() {
-            for x in 0..grid.width() {
-                let color = grid.get_pixel(x, y);
-                let red = ((color >> 16) & 0xFF) as u8;
-                let green = ((color >> 8) & 0xFF) as u8;
-                let blue = (color & 0xFF) as u8;
-                let color_str = format!("rgb({}, {}, {})", red, green, blue);
-                
-                self.context.set_fill_style(&color_str.into());
-                self.context.fill_rect(
-                    (x * self.pixel_size) as f64,
-                    (y * self.pixel_size) as f64,
-                    self.pixel_size as f64,
-                    self.pixel_size as f64,
-                );
+        let full = Viewport { x: 0, y: 0, width: grid.width(), height: grid.height() };
+        let view = self.viewport.unwrap_or(full).clamp_to(grid.width(), grid.height());
+        
+        // Resize canvas based on the viewport dimensions
+        let canvas_width = view.width * self.pixel_size;
+        let canvas_height = view.height * self.pixel_size;
+        self.canvas.set_width(canvas_width);
+        self.canvas.set_height(canvas_height);
+        if canvas_width == 0 || canvas_height == 0 {
+            return;
+        }
+
+        // Expand each visible 0xRRGGBB pixel into a pixel_size square of RGBA bytes
+        let pixels = &grid.inner.pixels;
+        let mut rgba = vec![0u8; (canvas_width * canvas_height * 4) as usize];
+        for (cy, row) in rgba.chunks_exact_mut((canvas_width * 4) as usize).enumerate() {
+            let y = view.y + cy as u32 / self.pixel_size;
+            for (cx, out) in row.chunks_exact_mut(4).enumerate() {
+                let x = view.x + cx as u32 / self.pixel_size;
+                let color = pixels[(y * grid.width() + x) as usize];
+                out.copy_from_slice(&[(color >> 16) as u8, (color >> 8) as u8, color as u8, 255]);
+            }
+        }
+        
+        // Mark the side of each eye cell it looks out of, once cells are big enough to show it
+        if grid.inner.show_eye_direction && self.pixel_size >= 3 {
+            let size = self.pixel_size;
+            for (x, y, direction) in grid.inner.eye_overlays() {
+                if !view.contains(x, y) {
+                    continue;
+                }
+                let (left, top) = ((x - view.x) * size, (y - view.y) * size);
+                let (mid_x, mid_y) = (left + size / 2, top + size / 2);
+                let (tick_x, tick_y) = match direction {
+                    Direction::Up => (mid_x, top),
+                    Direction::Right => (left + size - 1, mid_y),
+                    Direction::Down => (mid_x, top + size - 1),
+                    Direction::Left => (left, mid_y),
+                };
+                let i = ((tick_y * canvas_width + tick_x) * 4) as usize;
+                rgba[i..i + 4].copy_from_slice(&[255, 255, 255, 255]);
             }
         }
+
+        let image = ImageData::new_with_u8_clamped_array_and_sh(Clamped(&rgba), canvas_width, canvas_height)
+            .expect("image size matches the canvas");
+        self.context.put_image_data(&image, 0.0, 0.0).expect("failed to draw the grid");
     }
 }
 
+/// Cell state for a `set_cell` index, in `CellState` declaration order
+fn cell_state(idx: u8) -> Option<CellState> {
+    Some(match idx {
+        0 => CellState::Empty,
+        1 => CellState::Food,
+        2 => CellState::Wall,
+        3 => CellState::Mouth,
+        4 => CellState::Producer,
+        5 => CellState::Mover,
+        6 => CellState::Killer,
+        7 => CellState::Armor,
+        8 => CellState::Eye,
+        9 => CellState::Heal,
+        10 => CellState::Hazard,
+        _ => return None,
+    })
+}
+
+/// Build a plain JS object with numeric properties
+fn js_object(fields: &[(&str, f64)]) -> JsValue {
+    let object = js_sys::Object::new();
+    for (key, value) in fields.iter() {
+        js_sys::Reflect::set(&object, &JsValue::from_str(key), &JsValue::from_f64(*value))
+            .expect("setting a property on a plain object cannot fail");
+    }
+    object.into()
+}
+
 /// Starts an animation loop that updates the grid and re-renders it.
 #[wasm_bindgen]
 pub fn start_animation(renderer: Renderer, grid: WasmGrid) {
@@ -271,4 +882,114 @@ pub fn start_animation(renderer: Renderer, grid: WasmGrid) {
             g.borrow().as_ref().unwrap().as_ref().unchecked_ref()
         )
         .expect("should register requestAnimationFrame OK");
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_grid() -> WasmGrid {
+        let mut grid = WasmGrid::new_seeded(30, 20, 1).ok().unwrap();
+        grid.origin_of_life();
+        grid
+    }
+
+    #[test]
+    fn pixels_ptr_covers_the_whole_buffer() {
+        let mut grid = seeded_grid();
+        grid.step();
+        assert_eq!(grid.pixels_len(), 30 * 20);
+        let pixels = unsafe { std::slice::from_raw_parts(grid.pixels_ptr(), grid.pixels_len()) };
+        assert_eq!(pixels, grid.inner.pixels.as_slice());
+    }
+
+    #[test]
+    fn take_changes_flattens_to_position_and_color() {
+        let mut grid = seeded_grid();
+        grid.take_changes();
+        assert!(grid.set_cell(7, 3, CellState::Wall as u8));
+        assert_eq!(grid.take_changes(), vec![7, 3, grid.get_pixel(7, 3)]);
+        assert!(grid.take_changes().is_empty());
+    }
+
+    #[test]
+    fn organisms_flat_packs_every_organism_at_the_stride() {
+        let mut grid = seeded_grid();
+        grid.inner.organisms[0].food_collected = 100;
+        grid.step_n(5);
+        let flat = grid.organisms_flat();
+        assert!(grid.organism_count() > 1);
+        assert_eq!(flat.len(), grid.organism_count() * ORG_STRIDE);
+        for (i, fields) in flat.chunks(ORG_STRIDE).enumerate() {
+            let org = &grid.inner.organisms[i];
+            assert_eq!(fields[0], org.id as f32);
+            assert_eq!(fields[1], grid.organism_x(i).unwrap() as f32);
+            assert_eq!(fields[2], grid.organism_y(i).unwrap() as f32);
+            assert_eq!(fields[3], grid.organism_cell_count(i).unwrap() as f32);
+            assert_eq!(fields[4], grid.organism_food(i).unwrap() as f32);
+            assert_eq!(fields[5], grid.organism_lifetime(i).unwrap() as f32);
+            assert_eq!(fields[6], org.health as f32);
+            assert_eq!(fields[7], if grid.organism_is_alive(i).unwrap() { 1.0 } else { 0.0 });
+        }
+    }
+
+    #[test]
+    fn organism_getters_read_the_first_organism() {
+        let grid = seeded_grid();
+        assert_eq!(grid.organism_count(), 1);
+        assert_eq!(grid.organism_cell_count(0), Some(3));
+        assert_eq!(grid.organism_is_alive(0), Some(true));
+        assert_eq!(grid.organism_x(1), None);
+    }
+
+    #[test]
+    fn food_blocks_reproduction_decides_whether_organisms_spawn_on_food() {
+        let mut grid = WasmGrid::new_seeded(30, 20, 1).ok().unwrap();
+        assert!(grid.set_cell(11, 11, CellState::Food as u8)); // Under the basic organism's producer
+        grid.set_food_blocks_reproduction(true);
+        assert!(!grid.add_organism(10, 10));
+        assert_eq!(grid.organism_count(), 0);
+
+        grid.set_food_blocks_reproduction(false);
+        assert!(grid.add_organism(10, 10));
+        assert_eq!(grid.organism_count(), 1);
+        assert_eq!(grid.inner.get_cell(11, 11).unwrap().state, CellState::Producer);
+    }
+
+    #[test]
+    fn custom_templates_survive_a_genome_round_trip() {
+        let body = |org: &Organism| org.cells.iter()
+            .map(|cell| (cell.state, cell.x, cell.y, cell.direction))
+            .collect::<Vec<_>>();
+        for organism_type in 0..3 {
+            let mut grid = WasmGrid::new_seeded(30, 20, 1).ok().unwrap();
+            assert!(grid.add_custom_organism(5, 5, organism_type));
+            let original = grid.inner.organisms[0].clone();
+            let genome = original.to_genome();
+
+            let imported = Organism::from_genome(&genome).unwrap();
+            assert_eq!(body(&imported), body(&original), "template {}", organism_type);
+            assert_eq!((imported.mutability, imported.move_range), (original.mutability, original.move_range));
+            assert_eq!(imported.to_genome(), genome);
+
+            // Placed elsewhere, the copy takes up the same cells relative to its center
+            assert!(grid.add_organism_from_genome(20, 10, &genome));
+            let copy = &grid.inner.organisms[1];
+            assert_eq!(body(copy), body(&original));
+            for cell in &copy.cells {
+                let (x, y) = ((20 + cell.x) as u32, (10 + cell.y) as u32);
+                let placed = grid.inner.get_cell(x, y).unwrap();
+                assert_eq!((placed.state, placed.owner), (cell.state, Some(copy.id)));
+            }
+        }
+    }
+
+    #[test]
+    fn organism_from_genome_is_placed() {
+        let mut grid = seeded_grid();
+        let genome = grid.inner.organisms[0].to_genome();
+        assert!(grid.add_organism_from_genome(5, 5, &genome));
+        assert_eq!(grid.organism_count(), 2);
+        assert_eq!(grid.organism_cell_count(1), Some(3));
+    }
+}
@@ -7,7 +7,7 @@ extern "C" {
     fn log(s: &str);
     
     #[wasm_bindgen(js_namespace = console)]
-    fn error(s: &str);
+    pub fn error(s: &str);
 }
 
 // A macro to provide `println!`-style syntax for `console.log` logging.
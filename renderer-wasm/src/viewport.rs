@@ -0,0 +1,70 @@
+// renderer-wasm/src/viewport.rs
+
+/// Visible sub-rectangle of the grid, in cells
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Viewport {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Viewport {
+    /// Fit the viewport inside a `grid_width` x `grid_height` grid. The origin is pulled
+    /// back onto the grid and the size trimmed to what remains, keeping at least one cell.
+    pub fn clamp_to(self, grid_width: u32, grid_height: u32) -> Viewport {
+        let (x, width) = clamp_span(self.x, self.width, grid_width);
+        let (y, height) = clamp_span(self.y, self.height, grid_height);
+        Viewport { x, y, width, height }
+    }
+    
+    /// Whether cell (x, y) is inside the viewport
+    pub fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && y >= self.y && x - self.x < self.width && y - self.y < self.height
+    }
+}
+
+/// Clamp a start and length along one axis of `size` cells
+fn clamp_span(start: u32, len: u32, size: u32) -> (u32, u32) {
+    if size == 0 {
+        return (0, 0);
+    }
+    let start = start.min(size - 1);
+    (start, len.clamp(1, size - start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewport(x: u32, y: u32, width: u32, height: u32) -> Viewport {
+        Viewport { x, y, width, height }
+    }
+
+    #[test]
+    fn origin_past_the_edge_is_pulled_back_onto_the_grid() {
+        assert_eq!(viewport(50, 70, 10, 10).clamp_to(40, 30), viewport(39, 29, 1, 1));
+    }
+
+    #[test]
+    fn size_past_the_edge_is_trimmed() {
+        assert_eq!(viewport(30, 5, 20, 100).clamp_to(40, 30), viewport(30, 5, 10, 25));
+        assert_eq!(viewport(0, 0, 40, 30).clamp_to(40, 30), viewport(0, 0, 40, 30));
+    }
+
+    #[test]
+    fn zero_size_viewport_keeps_one_cell() {
+        let clamped = viewport(3, 4, 0, 0).clamp_to(40, 30);
+        assert_eq!(clamped, viewport(3, 4, 1, 1));
+        assert!(clamped.contains(3, 4));
+        assert!(!clamped.contains(4, 4));
+    }
+
+    #[test]
+    fn zero_size_grid_gives_an_empty_viewport() {
+        let clamped = viewport(3, 4, 10, 10).clamp_to(0, 0);
+        assert_eq!(clamped, viewport(0, 0, 0, 0));
+        assert!(!clamped.contains(0, 0));
+        assert_eq!(clamp_span(5, 5, 0), (0, 0));
+    }
+}
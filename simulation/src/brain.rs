@@ -0,0 +1,128 @@
+// simulation/src/brain.rs
+
+use rand::Rng;
+use crate::CellState;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How a mover reacts to a cell type its eyes see
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Reaction {
+    Ignore,
+    Chase,
+    Flee,
+}
+
+impl Reaction {
+    pub fn random(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..3) {
+            0 => Reaction::Ignore,
+            1 => Reaction::Chase,
+            _ => Reaction::Flee,
+        }
+    }
+
+    /// Genome letter: I, C or F
+    pub fn letter(self) -> char {
+        match self {
+            Reaction::Ignore => 'I',
+            Reaction::Chase => 'C',
+            Reaction::Flee => 'F',
+        }
+    }
+
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'I' => Some(Reaction::Ignore),
+            'C' => Some(Reaction::Chase),
+            'F' => Some(Reaction::Flee),
+            _ => None,
+        }
+    }
+}
+
+/// Heritable decision table: the reaction to each cell state, indexed like `Palette`.
+/// Empty cells are never sighted, so their entry is always `Ignore`.
+///
+/// ```
+/// use simulation::{Brain, CellState, Grid, Organism, Reaction};
+///
+/// for seed in 0..10 {
+///     let mut grid = Grid::new_seeded(40, 40, seed).unwrap();
+///     grid.food_production_prob = 0.0;
+///     let mut killer = Organism::new(0, 20, 15);
+///     killer.add_cell(CellState::Killer, 0, 1);
+///     grid.add_organism(killer);
+///
+///     // Eye facing up at the killer, mover behind
+///     let mut prey = Organism::new(0, 20, 18);
+///     prey.add_cell(CellState::Eye, 0, -1);
+///     prey.add_cell(CellState::Mover, 0, 1);
+///     let mut brain = Brain::default();
+///     brain.set_reaction(CellState::Killer, Reaction::Flee);
+///     prey.brain = Some(brain);
+///     grid.add_organism(prey);
+///
+///     for _ in 0..3 {
+///         grid.step();
+///     }
+///     assert!(grid.organisms[1].y > 18);
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Brain {
+    reactions: [Reaction; 11], // Indexed by cell state, in declaration order
+}
+
+impl Default for Brain {
+    fn default() -> Self {
+        Brain { reactions: [Reaction::Ignore; 11] }
+    }
+}
+
+impl Brain {
+    /// A brain with a random reaction to everything but empty cells
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let mut brain = Brain::default();
+        for reaction in brain.reactions.iter_mut().skip(1) {
+            *reaction = Reaction::random(rng);
+        }
+        brain
+    }
+
+    pub fn reaction(&self, state: CellState) -> Reaction {
+        self.reactions[state as usize]
+    }
+
+    pub fn set_reaction(&mut self, state: CellState, reaction: Reaction) {
+        if state != CellState::Empty {
+            self.reactions[state as usize] = reaction;
+        }
+    }
+
+    /// Re-roll the reaction to one random cell state
+    pub fn mutate(&mut self, rng: &mut impl Rng) {
+        let idx = rng.gen_range(1..self.reactions.len());
+        self.reactions[idx] = Reaction::random(rng);
+    }
+
+    /// One reaction letter per cell state, e.g. `IIFCIIFIIIF`
+    pub fn to_letters(&self) -> String {
+        self.reactions.iter().map(|reaction| reaction.letter()).collect()
+    }
+
+    /// Parse `to_letters` output; `None` unless it has one valid letter per state
+    pub fn from_letters(s: &str) -> Option<Self> {
+        let mut brain = Brain::default();
+        let mut letters = s.chars();
+        for reaction in brain.reactions.iter_mut() {
+            *reaction = Reaction::from_letter(letters.next()?)?;
+        }
+        if letters.next().is_some() || brain.reactions[0] != Reaction::Ignore {
+            return None;
+        }
+        Some(brain)
+    }
+}
@@ -0,0 +1,288 @@
+// simulation/src/builder.rs
+
+use std::fmt;
+use crate::Grid;
+
+/// Error returned when a grid can't be created with the requested settings
+///
+/// ```
+/// use simulation::{Grid, GridError};
+///
+/// assert_eq!(Grid::new(0, 10).err(), Some(GridError::ZeroSize { width: 0, height: 10 }));
+/// assert_eq!(Grid::new(10, 0).err(), Some(GridError::ZeroSize { width: 10, height: 0 }));
+/// assert_eq!(Grid::new(70_000, 70_000).err(), Some(GridError::TooLarge { width: 70_000, height: 70_000 }));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    ZeroSize { width: u32, height: u32 },
+    TooLarge { width: u32, height: u32 }, // More cells than cell indices (u32) can address
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::ZeroSize { width, height } => {
+                write!(f, "grid must be at least 1x1, got {}x{}", width, height)
+            }
+            GridError::TooLarge { width, height } => {
+                write!(f, "grid of {}x{} has more than {} cells", width, height, u32::MAX)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// Error returned by `Grid::try_set_cell` for a position off the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfBounds {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,  // Size of the grid written to
+    pub height: u32,
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cell ({}, {}) is outside the {}x{} grid", self.x, self.y, self.width, self.height)
+    }
+}
+
+impl std::error::Error for OutOfBounds {}
+
+/// Reject grid sizes the simulation can't index
+pub(crate) fn check_size(width: u32, height: u32) -> Result<(), GridError> {
+    if width == 0 || height == 0 {
+        return Err(GridError::ZeroSize { width, height });
+    }
+    if width.checked_mul(height).is_none() {
+        return Err(GridError::TooLarge { width, height });
+    }
+    Ok(())
+}
+
+/// Chainable configuration for a `Grid`. Anything left unset keeps the `Grid::new` default.
+///
+/// ```
+/// use simulation::GridBuilder;
+///
+/// let mut grid = GridBuilder::new()
+///     .width(200)
+///     .height(150)
+///     .seed(42)
+///     .food_production_prob(5.0)
+///     .max_organisms(500)
+///     .insta_kill(true)
+///     .build()
+///     .expect("grid size is non-zero");
+/// grid.origin_of_life();
+/// grid.step();
+/// assert_eq!(grid.width, 200);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GridBuilder {
+    width: u32,
+    height: u32,
+    seed: Option<u64>,
+    food_production_prob: Option<f32>,
+    max_organisms: Option<usize>,
+    lifespan_multiplier: Option<u32>,
+    insta_kill: Option<bool>,
+    food_blocks_reproduction: Option<bool>,
+    soft_cap_fraction: Option<f32>,
+    deterministic_placement: Option<bool>,
+    reproduction_cooldown: Option<u32>,
+}
+
+impl Default for GridBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GridBuilder {
+    /// Start from a 100x100 grid with entropy-seeded randomness
+    pub fn new() -> Self {
+        GridBuilder {
+            width: 100,
+            height: 100,
+            seed: None,
+            food_production_prob: None,
+            max_organisms: None,
+            lifespan_multiplier: None,
+            insta_kill: None,
+            food_blocks_reproduction: None,
+            soft_cap_fraction: None,
+            deterministic_placement: None,
+            reproduction_cooldown: None,
+        }
+    }
+
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Seed the simulation RNG so runs are reproducible
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn food_production_prob(mut self, prob: f32) -> Self {
+        self.food_production_prob = Some(prob);
+        self
+    }
+
+    pub fn max_organisms(mut self, max: usize) -> Self {
+        self.max_organisms = Some(max);
+        self
+    }
+
+    pub fn lifespan_multiplier(mut self, multiplier: u32) -> Self {
+        self.lifespan_multiplier = Some(multiplier);
+        self
+    }
+
+    pub fn insta_kill(mut self, insta_kill: bool) -> Self {
+        self.insta_kill = Some(insta_kill);
+        self
+    }
+
+    pub fn food_blocks_reproduction(mut self, blocks: bool) -> Self {
+        self.food_blocks_reproduction = Some(blocks);
+        self
+    }
+
+    /// Slow births down once the population passes this share of `max_organisms`
+    ///
+    /// ```
+    /// use simulation::GridBuilder;
+    ///
+    /// let births = |max_organisms| {
+    ///     let mut grid = GridBuilder::new()
+    ///         .width(200)
+    ///         .height(200)
+    ///         .seed(3)
+    ///         .food_production_prob(0.0)
+    ///         .max_organisms(max_organisms)
+    ///         .soft_cap_fraction(0.1)
+    ///         .build()
+    ///         .unwrap();
+    ///     for i in 0..20 {
+    ///         grid.create_basic_organism(20 + (i % 5) * 40, 20 + (i / 5) * 40);
+    ///     }
+    ///     for org in &mut grid.organisms {
+    ///         org.food_collected = 100;
+    ///     }
+    ///     grid.step();
+    ///     grid.organisms.len() - 20
+    /// };
+    /// // Room for all 20 offspring either way, but 20 of 40 is well past the soft cap
+    /// assert!(births(40) < births(1000));
+    /// ```
+    pub fn soft_cap_fraction(mut self, fraction: f32) -> Self {
+        self.soft_cap_fraction = Some(fraction);
+        self
+    }
+
+    /// Place offspring at the same spot every time, whatever the seed
+    ///
+    /// ```
+    /// use simulation::GridBuilder;
+    ///
+    /// let first_child = |seed| {
+    ///     let mut grid = GridBuilder::new()
+    ///         .width(40)
+    ///         .height(40)
+    ///         .seed(seed)
+    ///         .food_production_prob(0.0)
+    ///         .deterministic_placement(true)
+    ///         .build()
+    ///         .unwrap();
+    ///     grid.origin_of_life();
+    ///     grid.organisms[0].food_collected = 100;
+    ///     grid.step();
+    ///     let child = &grid.organisms[1];
+    ///     (child.x, child.y)
+    /// };
+    /// assert_eq!(first_child(1), (20, 16));
+    /// for seed in 2..10 {
+    ///     assert_eq!(first_child(seed), first_child(1));
+    /// }
+    /// ```
+    pub fn deterministic_placement(mut self, deterministic: bool) -> Self {
+        self.deterministic_placement = Some(deterministic);
+        self
+    }
+
+    /// Make organisms wait this many steps between births, however much food they have
+    ///
+    /// ```
+    /// use simulation::GridBuilder;
+    ///
+    /// let children = |cooldown| {
+    ///     let mut grid = GridBuilder::new()
+    ///         .width(80)
+    ///         .height(80)
+    ///         .seed(1)
+    ///         .food_production_prob(0.0)
+    ///         .reproduction_cooldown(cooldown)
+    ///         .build()
+    ///         .unwrap();
+    ///     grid.origin_of_life();
+    ///     let parent = grid.organisms[0].id;
+    ///     for _ in 0..20 {
+    ///         grid.organisms[0].food_collected = 100;
+    ///         grid.step();
+    ///     }
+    ///     grid.organisms.iter().filter(|org| org.parent_id == Some(parent)).count()
+    /// };
+    /// assert!(children(5) <= 20 / 5);
+    /// assert!(children(0) > 20 / 5);
+    /// // Never a second child within the 20 steps of a 20-step cooldown
+    /// assert!(children(20) <= 1);
+    /// ```
+    pub fn reproduction_cooldown(mut self, steps: u32) -> Self {
+        self.reproduction_cooldown = Some(steps);
+        self
+    }
+
+    /// Create the grid, rejecting a zero width or height or an oversized grid
+    pub fn build(self) -> Result<Grid, GridError> {
+        let mut grid = match self.seed {
+            Some(seed) => Grid::new_seeded(self.width, self.height, seed)?,
+            None => Grid::new(self.width, self.height)?,
+        };
+        if let Some(prob) = self.food_production_prob {
+            grid.food_production_prob = prob;
+        }
+        if let Some(max) = self.max_organisms {
+            grid.max_organisms = max;
+        }
+        if let Some(multiplier) = self.lifespan_multiplier {
+            grid.lifespan_multiplier = multiplier;
+        }
+        if let Some(insta_kill) = self.insta_kill {
+            grid.insta_kill = insta_kill;
+        }
+        if let Some(blocks) = self.food_blocks_reproduction {
+            grid.food_blocks_reproduction = blocks;
+        }
+        if let Some(fraction) = self.soft_cap_fraction {
+            grid.soft_cap_fraction = fraction;
+        }
+        if let Some(deterministic) = self.deterministic_placement {
+            grid.deterministic_placement = deterministic;
+        }
+        if let Some(steps) = self.reproduction_cooldown {
+            grid.reproduction_cooldown = steps;
+        }
+        Ok(grid)
+    }
+}
@@ -0,0 +1,103 @@
+// simulation/src/cell_set.rs
+
+/// Set of cell indices backed by a Fenwick tree, so membership changes and
+/// picking the n-th member in index order both take O(log n).
+/// Picking by rank keeps sampling independent of the order cells were added in.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct CellSet {
+    members: Vec<bool>, // Whether each cell index is in the set
+    tree: Vec<u32>,     // Fenwick tree of member counts, 1-based
+    len: usize,         // Number of members
+}
+
+impl CellSet {
+    /// Build a set over `members.len()` cells from a membership flag per cell
+    pub(crate) fn from_members(members: Vec<bool>) -> Self {
+        let n = members.len();
+        let mut tree = vec![0u32; n + 1];
+        for (i, &member) in members.iter().enumerate() {
+            tree[i + 1] += member as u32;
+            let parent = (i + 1) + ((i + 1) & (i + 1).wrapping_neg());
+            if parent <= n {
+                tree[parent] += tree[i + 1];
+            }
+        }
+        let len = members.iter().filter(|&&member| member).count();
+        CellSet { members, tree, len }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Add or remove a cell index
+    pub(crate) fn set(&mut self, idx: usize, member: bool) {
+        if self.members[idx] == member {
+            return;
+        }
+        self.members[idx] = member;
+        if member {
+            self.len += 1;
+        } else {
+            self.len -= 1;
+        }
+
+        let mut i = idx + 1;
+        while i < self.tree.len() {
+            if member {
+                self.tree[i] += 1;
+            } else {
+                self.tree[i] -= 1;
+            }
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// The `n`-th member (0-based) in increasing index order
+    pub(crate) fn nth(&self, n: usize) -> Option<usize> {
+        if n >= self.len {
+            return None;
+        }
+
+        // Walk down the tree, skipping whole blocks with too few members
+        let mut pos = 0;
+        let mut remaining = n as u32 + 1;
+        let mut step = (self.tree.len() - 1).next_power_of_two();
+        while step > 0 {
+            let next = pos + step;
+            if next < self.tree.len() && self.tree[next] < remaining {
+                pos = next;
+                remaining -= self.tree[next];
+            }
+            step >>= 1;
+        }
+        Some(pos) // 1-based position pos + 1, as a 0-based index
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn nth_picks_the_same_cell_as_scanning_in_index_order() {
+        let mut rng = ChaCha8Rng::seed_from_u64(7);
+        let mut members: Vec<bool> = (0..37).map(|_| rng.gen_bool(0.5)).collect();
+        let mut set = CellSet::from_members(members.clone());
+        for _ in 0..200 {
+            let idx = rng.gen_range(0..members.len());
+            let member = rng.gen_bool(0.5);
+            members[idx] = member;
+            set.set(idx, member);
+
+            let scanned: Vec<usize> = (0..members.len()).filter(|&i| members[i]).collect();
+            assert_eq!(set.len(), scanned.len());
+            for (n, &idx) in scanned.iter().enumerate() {
+                assert_eq!(set.nth(n), Some(idx));
+            }
+            assert_eq!(set.nth(scanned.len()), None);
+        }
+    }
+}
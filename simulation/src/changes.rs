@@ -0,0 +1,38 @@
+// simulation/src/changes.rs
+
+use crate::{Cell, CellState};
+
+/// Cells whose state changed since the log was last taken, with the state each one had
+/// before its first change. Cells that change and change back aren't reported.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ChangeLog {
+    before: Vec<Option<CellState>>, // State before the first change, per cell index
+    order: Vec<usize>,              // Changed cell indices, in order of first change
+}
+
+impl ChangeLog {
+    pub(crate) fn new(cell_count: usize) -> Self {
+        ChangeLog { before: vec![None; cell_count], order: Vec::new() }
+    }
+
+    /// Note that cell `idx` is about to change from state `before`
+    pub(crate) fn record(&mut self, idx: usize, before: CellState) {
+        if self.before[idx].is_none() {
+            self.before[idx] = Some(before);
+            self.order.push(idx);
+        }
+    }
+
+    /// Drain the log into `(x, y, new state)` entries for cells that really changed
+    pub(crate) fn take(&mut self, cells: &[Cell], width: u32) -> Vec<(u32, u32, CellState)> {
+        let mut changes = Vec::new();
+        for idx in self.order.drain(..) {
+            let before = self.before[idx].take();
+            let state = cells[idx].state;
+            if before != Some(state) {
+                changes.push((idx as u32 % width, idx as u32 / width, state));
+            }
+        }
+        changes
+    }
+}
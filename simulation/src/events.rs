@@ -0,0 +1,49 @@
+// simulation/src/events.rs
+
+use crate::DeathCause;
+
+/// Something that happened during a step, passed to the grid's event hook
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GridEvent {
+    OrganismBorn { id: usize, parent_id: usize },
+    OrganismDied { id: usize, cause: DeathCause },
+    FoodEaten { x: u32, y: u32 },
+    StepCompleted { tick: u64 },
+}
+
+/// What `Grid::set_on_event` accepts: any `FnMut(GridEvent)`, which with the `rayon`
+/// feature must also be `Send + Sync` so the grid can still be shared between threads
+#[cfg(feature = "rayon")]
+pub trait EventCallback: FnMut(GridEvent) + Send + Sync {}
+#[cfg(feature = "rayon")]
+impl<F: FnMut(GridEvent) + Send + Sync> EventCallback for F {}
+
+/// What `Grid::set_on_event` accepts: any `FnMut(GridEvent)`
+#[cfg(not(feature = "rayon"))]
+pub trait EventCallback: FnMut(GridEvent) {}
+#[cfg(not(feature = "rayon"))]
+impl<F: FnMut(GridEvent)> EventCallback for F {}
+
+/// Callback set with `Grid::set_on_event`. A closure can't be copied, so a cloned
+/// grid (or a snapshot) starts without one.
+#[derive(Default)]
+pub(crate) struct EventHook(Option<Box<dyn EventCallback>>);
+
+impl EventHook {
+    pub(crate) fn new(hook: Box<dyn EventCallback>) -> Self {
+        EventHook(Some(hook))
+    }
+
+    /// Pass `event` to the callback, if there is one
+    pub(crate) fn emit(&mut self, event: GridEvent) {
+        if let Some(hook) = &mut self.0 {
+            hook(event);
+        }
+    }
+}
+
+impl Clone for EventHook {
+    fn clone(&self) -> Self {
+        EventHook(None)
+    }
+}
@@ -0,0 +1,114 @@
+// simulation/src/geometry.rs
+
+/// Cells on the straight line between two points, both ends included (Bresenham).
+/// Works in every octant; a zero-length line yields its single point.
+#[derive(Clone, Debug)]
+pub struct BresenhamLine {
+    x: i32,
+    y: i32,
+    x2: i32,
+    y2: i32,
+    dx: i32,
+    dy: i32,
+    sx: i32,
+    sy: i32,
+    err: i32,
+    done: bool,
+}
+
+impl BresenhamLine {
+    pub fn new(x1: i32, y1: i32, x2: i32, y2: i32) -> Self {
+        let dx = (x2 - x1).abs();
+        let dy = (y2 - y1).abs();
+        BresenhamLine {
+            x: x1,
+            y: y1,
+            x2,
+            y2,
+            dx,
+            dy,
+            sx: if x1 < x2 { 1 } else { -1 },
+            sy: if y1 < y2 { 1 } else { -1 },
+            err: dx - dy,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for BresenhamLine {
+    type Item = (i32, i32);
+
+    fn next(&mut self) -> Option<(i32, i32)> {
+        if self.done {
+            return None;
+        }
+        let point = (self.x, self.y);
+        if self.x == self.x2 && self.y == self.y2 {
+            self.done = true;
+            return Some(point);
+        }
+
+        let e2 = 2 * self.err;
+        if e2 > -self.dy {
+            self.err -= self.dy;
+            self.x += self.sx;
+        }
+        if e2 < self.dx {
+            self.err += self.dx;
+            self.y += self.sy;
+        }
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(x1: i32, y1: i32, x2: i32, y2: i32) -> Vec<(i32, i32)> {
+        BresenhamLine::new(x1, y1, x2, y2).collect()
+    }
+
+    #[test]
+    fn zero_length_line_is_one_point() {
+        assert_eq!(line(3, -2, 3, -2), vec![(3, -2)]);
+    }
+
+    #[test]
+    fn straight_and_diagonal_lines() {
+        assert_eq!(line(0, 0, 3, 0), vec![(0, 0), (1, 0), (2, 0), (3, 0)]);
+        assert_eq!(line(0, 0, 0, -3), vec![(0, 0), (0, -1), (0, -2), (0, -3)]);
+        assert_eq!(line(0, 0, 3, 3), vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+        assert_eq!(line(2, 2, -1, 5), vec![(2, 2), (1, 3), (0, 4), (-1, 5)]);
+    }
+
+    #[test]
+    fn steep_slope() {
+        assert_eq!(line(0, 0, 2, 5), vec![(0, 0), (0, 1), (1, 2), (1, 3), (2, 4), (2, 5)]);
+    }
+
+    #[test]
+    fn every_octant_steps_one_cell_at_a_time_between_the_ends() {
+        let ends = [(5, 2), (2, 5), (-2, 5), (-5, 2), (-5, -2), (-2, -5), (2, -5), (5, -2)];
+        for (x2, y2) in ends {
+            let points = line(0, 0, x2, y2);
+            assert_eq!(points.first(), Some(&(0, 0)));
+            assert_eq!(points.last(), Some(&(x2, y2)));
+            // One point per step along the longer axis
+            assert_eq!(points.len() as i32, x2.abs().max(y2.abs()) + 1);
+            for pair in points.windows(2) {
+                let (dx, dy) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+                assert!(dx.abs() <= 1 && dy.abs() <= 1);
+                assert!(dx == 0 || dx == x2.signum());
+                assert!(dy == 0 || dy == y2.signum());
+            }
+        }
+    }
+
+    #[test]
+    fn reversed_line_visits_the_same_cells_on_symmetric_slopes() {
+        let mut back = line(4, 4, 0, 0);
+        back.reverse();
+        assert_eq!(back, line(0, 0, 4, 4));
+    }
+}
@@ -1,8 +1,30 @@
 // simulation/src/lib.rs
 
-use rand::random;
+use std::collections::{BTreeMap, HashMap};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 mod organism;
-pub use organism::{Organism, Direction, OrganismCell};
+mod stats;
+mod save;
+mod cell_set;
+mod builder;
+mod geometry;
+mod palette;
+mod changes;
+mod events;
+mod brain;
+use cell_set::CellSet;
+use changes::ChangeLog;
+use events::EventHook;
+pub use organism::{Organism, Direction, DeathCause, OrganismAction, OrganismCell, OrganismParseError, GenomeParseError, AsciiParseError, MutationParams};
+pub use stats::{GridStats, SimStats, DeathStats, RunSummary, run_headless};
+pub use builder::{GridBuilder, GridError, OutOfBounds};
+pub use geometry::BresenhamLine;
+pub use palette::{Palette, RenderMode};
+pub use events::{EventCallback, GridEvent};
+pub use brain::{Brain, Reaction};
 
 // Special RNG initialization for WASM targets
 // This could be added at the top of organism.rs or lib.rs in the simulation crate
@@ -24,8 +46,9 @@ pub fn initialize() {
     init_random();
 }
 /// Different types of cells in the simulation
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
-pub enum CellStates {
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CellState {
     Empty,
     Food,
     Wall,
@@ -35,57 +58,418 @@ pub enum CellStates {
     Killer,
     Armor,
     Eye,
+    Heal,
+    Hazard, // Environment cell that damages any organism cell touching it
 }
 
-impl CellStates {
+impl CellState {
     /// Convert a cell state to a color representation
     pub fn to_color(&self) -> u32 {
         match self {
-            CellStates::Empty => 0x0E1318,   // Dark blue
-            CellStates::Food => 0x2F7AB7,    // Bluish
-            CellStates::Wall => 0x808080,    // Gray
-            CellStates::Mouth => 0xDEB14D,   // Orange
-            CellStates::Producer => 0x15DE59, // Green
-            CellStates::Mover => 0x60D4FF,   // Light blue
-            CellStates::Killer => 0xF82380,  // Red
-            CellStates::Armor => 0x7230DB,   // Purple
-            CellStates::Eye => 0xB6C1EA,     // Light purple
+            CellState::Empty => 0x0E1318,   // Dark blue
+            CellState::Food => 0x2F7AB7,    // Bluish
+            CellState::Wall => 0x808080,    // Gray
+            CellState::Mouth => 0xDEB14D,   // Orange
+            CellState::Producer => 0x15DE59, // Green
+            CellState::Mover => 0x60D4FF,   // Light blue
+            CellState::Killer => 0xF82380,  // Red
+            CellState::Armor => 0x7230DB,   // Purple
+            CellState::Eye => 0xB6C1EA,     // Light purple
+            CellState::Heal => 0xF2E85C,    // Yellow
+            CellState::Hazard => 0xD81BD8,  // Magenta
         }
     }
+    
+    /// Whether this state belongs to the environment rather than to an organism's body
+    pub fn is_environment(&self) -> bool {
+        matches!(self, CellState::Empty | CellState::Food | CellState::Wall | CellState::Hazard)
+    }
+}
+
+/// Color for an organism species, derived from its anatomy hash.
+/// Every channel stays above 0x40 so species never blend into the dark background.
+pub fn species_color(hash: u64) -> u32 {
+    let r = 0x40 + (hash & 0xBF) as u32;
+    let g = 0x40 + ((hash >> 8) & 0xBF) as u32;
+    let b = 0x40 + ((hash >> 16) & 0xBF) as u32;
+    (r << 16) | (g << 8) | b
+}
+
+/// Color for an organism ID, scattered so consecutive IDs look different
+fn owner_color(id: usize) -> u32 {
+    species_color((id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40)
+}
+
+/// Green for a newborn fading to red for an organism at the end of its lifespan
+fn age_color(fraction: f32) -> u32 {
+    let old = (fraction.clamp(0.0, 1.0) * 255.0) as u32;
+    (old << 16) | ((255 - old) << 8) | 0x20
+}
+
+/// Black for no food nearby, through red to yellow for a neighbourhood full of food
+fn heat_color(density: f32) -> u32 {
+    let heat = (density.clamp(0.0, 1.0) * 510.0) as u32;
+    (heat.min(255) << 16) | (heat.saturating_sub(255) << 8)
+}
+
+/// Tint for an eye looking this way: red up, green right, blue down, yellow left
+fn eye_direction_tint(direction: Direction) -> u32 {
+    match direction {
+        Direction::Up => 0xFF4040,
+        Direction::Right => 0x40FF40,
+        Direction::Down => 0x4040FF,
+        Direction::Left => 0xFFFF40,
+    }
+}
+
+/// Even mix of two 0xRRGGBB colors
+fn blend_colors(a: u32, b: u32) -> u32 {
+    // Halve each channel before adding so no channel carries into the next
+    ((a >> 1) & 0x7F7F7F) + ((b >> 1) & 0x7F7F7F)
 }
 
 /// Cell in the grid, includes state and owner
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Cell {
-    pub state: CellStates,
+    pub state: CellState,
     pub owner: Option<usize>, // Index of the owning organism, if any
+    pub food_age: u32,        // Steps this cell has been food; 0 for any other state
+}
+
+/// What an organism sensed before anything moved
+struct OrganismSenses {
+    sighting: Option<(Direction, CellState)>, // What its eyes saw, and which way
+    smell: Option<f32>,                       // Share of the cells around it holding food
+    touch_dir: Option<Direction>,             // Way to the richest food nearby
+    touching: bool,                           // Food is next to its mouth
 }
 
 /// The core Grid business logic with no WASM/browser dependencies.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Grid {
     pub width: u32,
     pub height: u32,
-    pub pixels: Vec<u32>,
-    pub cells: Vec<Cell>,
+    pub pixels: Vec<u32>,          // Kept in sync with `cells` by `set_cell`
+    pub cells: Vec<Cell>,          // Change through `set_cell` so empty cells and pixels stay in sync
+    pub light: Vec<f32>,           // Light level per cell (0.0-1.0); scales how often producers make food
     pub food_production_prob: f32, // Probability for producer cells (0.0-1.0)
     pub food_drop_prob: f32,       // Probability for random food generation (0.0-1.0)
     pub organisms: Vec<Organism>,  // All organisms in the simulation
-    pub next_organism_id: usize,   // Next ID to assign to a new organism
+    pub next_organism_id: usize,   // Next ID to assign to a new organism; IDs are never reused, even after `reset`
     pub max_organisms: usize,      // Maximum number of organisms allowed
     pub lifespan_multiplier: u32,  // Multiplier for organism lifespan
     pub insta_kill: bool,          // Whether organisms die instantly when hit by a killer
+    pub heal_interval: u32,        // Steps between each heal cell restoring 1 health (0 = no healing)
+    pub killer_damage: u32,        // Damage each killer cell deals to each adjacent foreign cell
+    pub armor_reduction: u32,      // Damage an armor cell takes off each hit it receives
+    pub armor_blocks_adjacent: bool, // Armor also shields its own organism's cells on either side of it
+    pub use_diagonal_neighbors: bool, // Eat, kill and produce into all 8 neighbours instead of 4
     pub food_blocks_reproduction: bool,  // Add this field
     pub movers_can_produce: bool, // Default to false
+    pub eye_view_distance: u32,   // How many tiles an eye cell can see
+    pub sense_radius: u32,        // How far eyeless movers smell food and linger near it (0 = off)
+    pub food_gradient_movement: bool, // Eyeless movers head toward the richest nearby food when turning
+    pub brains_enabled: bool,     // Organisms placed without a brain get a random one; offspring inherit theirs
+    pub wrap_edges: bool,         // Whether the grid wraps around at its edges (toroidal); pointless with border walls
+    pub producer_energy_cost: u32, // Energy a producer organism spends per food it makes
+    pub move_energy_cost: u32,    // Energy a mover organism spends per step it moves
+    pub move_food_cost: f32,      // Collected food a mover spends per step it moves, may be fractional (0 = free)
+    pub starvation_steps: u32,    // Steps a mover can go unable to pay for a move, with no food at its mouth, before it starves
+    pub energy_regen: u32,        // Energy every organism regains per step, unless metabolism is on
+    pub metabolism_enabled: bool, // Energy comes only from food, every cell costs upkeep and empty organisms starve
+    pub cell_upkeep: u32,         // Energy each cell costs per step under metabolism
+    pub food_energy: u32,         // Energy gained per food eaten under metabolism
+    pub mutation_params: MutationParams, // Mutation chances for offspring
+    pub max_organism_cells: usize, // Largest body mutation can grow and that may reproduce (0 = unlimited)
+    pub extra_mover_food_cost: u32, // Food a mover needs to reproduce on top of one per cell
+    pub reproduction_cost_multiplier: f32, // Scales the food needed to reproduce, rounded up
+    pub soft_cap_fraction: f32,   // Share of max_organisms above which births get increasingly rare (1.0 = off)
+    pub deterministic_placement: bool, // Offspring always try the same birth spot, then alternatives in a fixed order
+    pub reproduction_cooldown: u32, // Steps an organism waits after a birth (or its own) before it may reproduce again (0 = off)
+    pub sim_stats: SimStats,      // Counts for the most recent step
+    pub death_stats: DeathStats,  // Deaths by cause since the grid was created or last reset
+    pub stats_history: Vec<SimStats>, // Counts for recent steps, oldest first
+    pub stats_history_len: usize, // How many steps of history to keep
+    pub recent_deaths: Vec<(usize, DeathCause)>, // Organisms removed during the last step
+    pub recent_actions: Vec<(usize, OrganismAction)>, // What each organism did during the last step
+    pub color_by_species: bool,   // Color organism cells by species instead of cell type
+    pub color_by_owner: bool,     // Tint organism cells with their species color, keeping the cell type visible
+    pub show_eye_direction: bool, // Tint eye cells by the way they look (see `eye_overlays`)
+    pub food_lifespan: Option<u32>, // Steps before uneaten food spoils, None to keep it forever
+    pub sparse_food_scan: bool, // Spoil and count food by visiting food cells only, not every cell; same results, faster on sparse grids
+    pub palette: Palette,         // Cell colors; change through `set_palette` so pixels follow
+    pub render_mode: RenderMode,  // What pixels show; change through `set_render_mode` so pixels follow
+    pub tick_count: u64,          // Steps run since the grid was created or last reset
+    pub rng: ChaCha8Rng,          // Source of all simulation randomness
+    default_anatomy: Option<Vec<(CellState, i32, i32)>>, // Layout for `create_basic_organism`; change through `set_default_anatomy`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    empty_cells: CellSet,         // Indices of all empty cells, for sampling food drops
+    #[cfg_attr(feature = "serde", serde(skip))]
+    food_cells: CellSet,          // Indices of all food cells, for sparse_food_scan
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hazard_cells: usize,          // Number of hazard cells, so grids without any skip the hazard pass
+    #[cfg_attr(feature = "serde", serde(skip))]
+    changes: ChangeLog,           // Cells changed since the last `take_changes`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    on_event: EventHook,          // Callback for births, deaths, meals and finished steps
 
 }
 
 impl Grid {
-    pub fn new(width: u32, height: u32) -> Self {
+    /// Create a grid whose randomness is seeded from system entropy.
+    /// Fails if either side is zero or the grid has more cells than fit in a `u32`.
+    pub fn new(width: u32, height: u32) -> Result<Self, GridError> {
+        builder::check_size(width, height)?;
+        Ok(Self::with_rng(width, height, ChaCha8Rng::from_entropy()))
+    }
+    
+    /// Create a grid with a fixed seed, so identical seeds give identical runs.
+    /// Fails on the same sizes as `new`.
+    pub fn new_seeded(width: u32, height: u32, seed: u64) -> Result<Self, GridError> {
+        builder::check_size(width, height)?;
+        Ok(Self::new_seeded_unchecked(width, height, seed))
+    }
+    
+    /// `new_seeded` for sizes already known to be valid
+    pub(crate) fn new_seeded_unchecked(width: u32, height: u32, seed: u64) -> Self {
+        Self::with_rng(width, height, ChaCha8Rng::seed_from_u64(seed))
+    }
+    
+    /// Snapshot the whole simulation, RNG state included, as JSON
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("grid is always serializable")
+    }
+    
+    /// Restore a simulation saved with `to_json`.
+    /// Snapshots missing any field are rejected rather than partially loaded.
+    #[cfg(feature = "serde")]
+    pub fn from_json(s: &str) -> Result<Grid, serde_json::Error> {
+        let mut grid: Grid = serde_json::from_str(s)?;
+        let expected = (grid.width * grid.height) as usize;
+        if grid.cells.len() != expected || grid.pixels.len() != expected || grid.light.len() != expected {
+            return Err(serde::de::Error::custom(format!(
+                "grid is {}x{} but holds {} cells, {} pixels and {} light levels",
+                grid.width, grid.height, grid.cells.len(), grid.pixels.len(), grid.light.len()
+            )));
+        }
+        grid.rebuild_cell_sets();
+        grid.changes = ChangeLog::new(expected);
+        Ok(grid)
+    }
+    
+    /// Copy of the whole simulation (cells, organisms, parameters and RNG state) for `restore`
+    pub fn snapshot(&self) -> Grid {
+        self.clone()
+    }
+    
+    /// Return to a state taken with `snapshot`. Stepping afterwards replays exactly what
+    /// followed the snapshot. Cells that differ are reported by `take_changes`,
+    /// and the event hook stays in place.
+    pub fn restore(&mut self, snapshot: &Grid) {
+        let on_event = std::mem::take(&mut self.on_event);
+        let mut changes = std::mem::take(&mut self.changes);
+        if self.cells.len() == snapshot.cells.len() {
+            for (idx, (now, then)) in self.cells.iter().zip(&snapshot.cells).enumerate() {
+                if now.state != then.state {
+                    changes.record(idx, now.state);
+                }
+            }
+        } else {
+            changes = ChangeLog::new(snapshot.cells.len());
+        }
+        *self = snapshot.clone();
+        self.changes = changes;
+        self.on_event = on_event;
+    }
+    
+    /// Change the grid size, keeping the overlapping top-left region. Organisms that no
+    /// longer fit entirely are removed along with their cells; new cells start empty and
+    /// fully lit. Fails, leaving the grid unchanged, on sizes `new` would reject.
+    pub fn resize(&mut self, new_width: u32, new_height: u32) -> Result<(), GridError> {
+        builder::check_size(new_width, new_height)?;
+        
+        // Lift organisms that would stick out of the new bounds
+        let fits = |org: &Organism| org.cells.iter().all(|cell| {
+            org.get_cell_position(cell).is_some_and(|(x, y)| x < new_width && y < new_height)
+        });
+        let (kept, dropped): (Vec<Organism>, Vec<Organism>) =
+            std::mem::take(&mut self.organisms).into_iter().partition(fits);
+        for org in &dropped {
+            for cell in &org.cells {
+                if let Some((x, y)) = self.cell_position(org, cell) {
+                    if self.get_cell(x, y).is_some_and(|cell| cell.owner == Some(org.id)) {
+                        self.set_cell(x, y, CellState::Empty, None);
+                    }
+                }
+            }
+        }
+        self.organisms = kept;
+        
+        // Copy the overlap into fresh buffers
+        let count = (new_width * new_height) as usize;
+        let mut cells = vec![Cell { state: CellState::Empty, owner: None, food_age: 0 }; count];
+        let mut light = vec![1.0; count];
+        for y in 0..self.height.min(new_height) {
+            for x in 0..self.width.min(new_width) {
+                let old_idx = (y * self.width + x) as usize;
+                let new_idx = (y * new_width + x) as usize;
+                cells[new_idx] = self.cells[old_idx].clone();
+                light[new_idx] = self.light[old_idx];
+            }
+        }
+        
+        self.width = new_width;
+        self.height = new_height;
+        self.cells = cells;
+        self.light = light;
+        self.pixels = vec![0; count];
+        self.refresh_pixels();
+        self.rebuild_cell_sets();
+        self.changes = ChangeLog::new(count);
+        Ok(())
+    }
+    
+    /// Restart the simulation RNG from a fixed seed
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+    }
+    
+    /// Draw a wall along the straight line from (x0, y0) to (x1, y1).
+    /// Cells held by organisms and cells off the grid are skipped.
+    pub fn place_wall_line(&mut self, x0: u32, y0: u32, x1: u32, y1: u32) {
+        for (x, y) in BresenhamLine::new(x0 as i32, y0 as i32, x1 as i32, y1 as i32) {
+            self.place_wall(x as u32, y as u32);
+        }
+    }
+    
+    /// Wall in the border of a `w` x `h` rectangle with its top-left corner at (x, y),
+    /// leaving the inside alone. Cells held by organisms and cells off the grid are skipped.
+    pub fn place_wall_rect(&mut self, x: u32, y: u32, w: u32, h: u32) {
+        self.draw_wall_rect(x, y, w, h, false);
+    }
+    
+    /// Draw a `w` x `h` rectangle of wall with its top-left corner at (x, y), either solid or just the border
+    pub fn draw_wall_rect(&mut self, x: u32, y: u32, w: u32, h: u32, filled: bool) {
+        self.draw_rect(x, y, w, h, filled, CellState::Wall);
+    }
+    
+    /// Draw a rectangle of hazard, like `draw_wall_rect`. Organism cells touching a hazard
+    /// take killer damage each step, and organisms can't move onto one.
+    ///
+    /// ```
+    /// use simulation::{CellState, Grid};
+    ///
+    /// let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+    /// grid.food_production_prob = 0.0;
+    /// grid.draw_hazard_rect(9, 8, 1, 1, true);
+    /// grid.create_basic_organism(10, 10); // Producer at (9, 9) touches the hazard
+    /// grid.step();
+    /// assert_eq!(grid.organisms[0].health, 2);
+    /// assert_eq!(grid.get_cell(9, 8).unwrap().state, CellState::Hazard);
+    /// ```
+    pub fn draw_hazard_rect(&mut self, x: u32, y: u32, w: u32, h: u32, filled: bool) {
+        self.draw_rect(x, y, w, h, filled, CellState::Hazard);
+    }
+    
+    fn draw_rect(&mut self, x: u32, y: u32, w: u32, h: u32, filled: bool, state: CellState) {
+        if w == 0 || h == 0 || x >= self.width || y >= self.height {
+            return;
+        }
+        // Far corner of the full rectangle, which may lie off the grid
+        let (x1, y1) = (x.saturating_add(w - 1), y.saturating_add(h - 1));
+        
+        // Only visit the part that is on the grid; border sides off the grid are clipped away
+        for wy in y..=y1.min(self.height - 1) {
+            for wx in x..=x1.min(self.width - 1) {
+                if filled || wx == x || wx == x1 || wy == y || wy == y1 {
+                    self.place_terrain(wx, wy, state);
+                }
+            }
+        }
+    }
+    
+    /// Wall off the outermost ring of cells. Like any wall, `reset(false)` keeps it.
+    /// It makes `wrap_edges` moot, since nothing can cross the edge anyway; use one or the other.
+    ///
+    /// ```
+    /// use simulation::{CellState, Grid};
+    ///
+    /// let mut grid = Grid::new_seeded(10, 8, 1).unwrap();
+    /// grid.add_border_walls();
+    /// for y in 0..8 {
+    ///     for x in 0..10 {
+    ///         let edge = x == 0 || y == 0 || x == 9 || y == 7;
+    ///         let expected = if edge { CellState::Wall } else { CellState::Empty };
+    ///         assert_eq!(grid.get_cell(x, y).unwrap().state, expected);
+    ///     }
+    /// }
+    ///
+    /// grid.reset(false);
+    /// assert_eq!(grid.get_cell(0, 0).unwrap().state, CellState::Wall);
+    /// grid.origin_of_life();
+    /// assert_eq!(grid.organisms.len(), 1);
+    /// ```
+    pub fn add_border_walls(&mut self) {
+        let (width, height) = (self.width, self.height);
+        self.draw_wall_rect(0, 0, width, height, false);
+    }
+    
+    /// Turn a wall cell back into an empty cell; any other cell is left alone
+    pub fn clear_wall(&mut self, x: u32, y: u32) {
+        if self.get_cell(x, y).is_some_and(|cell| cell.state == CellState::Wall) {
+            self.set_cell(x, y, CellState::Empty, None);
+        }
+    }
+    
+    /// Put a wall on an in-bounds cell that no organism is using
+    fn place_wall(&mut self, x: u32, y: u32) {
+        self.place_terrain(x, y, CellState::Wall);
+    }
+    
+    /// Put a wall or hazard on an in-bounds cell that no organism is using
+    fn place_terrain(&mut self, x: u32, y: u32, state: CellState) {
+        if x < self.width && y < self.height && self.get_cell(x, y).is_some_and(|cell| cell.owner.is_none()) {
+            self.set_cell(x, y, state, None);
+        }
+    }
+    
+    /// Call `hook` with every birth, death, meal and finished step from now on,
+    /// replacing any earlier hook. Clones and snapshots of the grid don't keep it.
+    pub fn set_on_event(&mut self, hook: impl EventCallback + 'static) {
+        self.on_event = EventHook::new(Box::new(hook));
+    }
+    
+    /// Stop reporting events
+    pub fn clear_on_event(&mut self) {
+        self.on_event = EventHook::default();
+    }
+    
+    /// Organisms removed during the last step, with why they died
+    pub fn recent_deaths(&self) -> &[(usize, DeathCause)] {
+        &self.recent_deaths
+    }
+    
+    /// What each organism updated during the last step did, by organism ID.
+    /// Organisms that starved before getting to act aren't listed.
+    pub fn recent_actions(&self) -> &[(usize, OrganismAction)] {
+        &self.recent_actions
+    }
+    
+    /// Set the mutation chances used for offspring
+    pub fn set_mutation_params(&mut self, params: MutationParams) {
+        self.mutation_params = params;
+    }
+    
+    fn with_rng(width: u32, height: u32, rng: ChaCha8Rng) -> Self {
         Self {
             width,
             height,
-            pixels: vec![0; (width * height) as usize],
-            cells: vec![Cell { state: CellStates::Empty, owner: None }; (width * height) as usize],
+            pixels: vec![CellState::Empty.to_color(); (width * height) as usize],
+            cells: vec![Cell { state: CellState::Empty, owner: None, food_age: 0 }; (width * height) as usize],
+            light: vec![1.0; (width * height) as usize], // Fully lit until a gradient is set
             food_production_prob: 0.05, // 5% chance by default (matches JS default)
             food_drop_prob: 0.0,        // 0% chance by default (no random food)
             organisms: Vec::new(),
@@ -93,10 +477,54 @@ impl Grid {
             max_organisms: 1000,       // Default max organisms
             lifespan_multiplier: 100,  // Default lifespan multiplier
             insta_kill: false,         // Default to not insta-kill
+            heal_interval: 5,
+            killer_damage: 1,
+            armor_reduction: 1,        // Armor fully blocks the default damage
+            armor_blocks_adjacent: false,
+            use_diagonal_neighbors: false,
             food_blocks_reproduction: true, // Default to food blocking reproduction
             movers_can_produce: false,  // Default to false like in JS
-
-
+            eye_view_distance: 10,      // Default eye range
+            sense_radius: 0,            // Movers roam regardless of nearby food
+            food_gradient_movement: false,
+            brains_enabled: false,
+            wrap_edges: false,          // Default to a bounded grid
+            producer_energy_cost: 2,    // Producing costs more than an organism regains in a step
+            move_energy_cost: 1,
+            move_food_cost: 0.0,        // Moving only costs energy
+            starvation_steps: 10,
+            energy_regen: 1,
+            metabolism_enabled: false,
+            cell_upkeep: 1,
+            food_energy: 10,
+            mutation_params: MutationParams::default(),
+            max_organism_cells: 0,
+            extra_mover_food_cost: 1,   // Matches the JS extraMoverFoodCost
+            reproduction_cost_multiplier: 1.0,
+            soft_cap_fraction: 1.0,     // Hard cap only
+            deterministic_placement: false,
+            reproduction_cooldown: 0,
+            sim_stats: SimStats::default(),
+            death_stats: DeathStats::default(),
+            stats_history: Vec::new(),
+            stats_history_len: 1000,
+            recent_deaths: Vec::new(),
+            recent_actions: Vec::new(),
+            color_by_species: false,
+            color_by_owner: false,
+            show_eye_direction: false,
+            food_lifespan: None,
+            sparse_food_scan: false,
+            palette: Palette::default(),
+            render_mode: RenderMode::default(),
+            tick_count: 0,
+            rng,
+            default_anatomy: None,
+            empty_cells: CellSet::from_members(vec![true; (width * height) as usize]),
+            food_cells: CellSet::from_members(vec![false; (width * height) as usize]),
+            hazard_cells: 0,
+            changes: ChangeLog::new((width * height) as usize),
+            on_event: EventHook::default(),
         }
     }
 
@@ -126,29 +554,391 @@ impl Grid {
         }
     }
 
-    /// Set a cell's state and owner
-    pub fn set_cell(&mut self, x: u32, y: u32, state: CellStates, owner: Option<usize>) {
+    /// Light a grid from `top` at the first row to `bottom` at the last, both clamped to 0.0-1.0
+    pub fn set_light_gradient(&mut self, top: f32, bottom: f32) {
+        let top = top.clamp(0.0, 1.0);
+        let bottom = bottom.clamp(0.0, 1.0);
+        for y in 0..self.height {
+            let t = if self.height > 1 { y as f32 / (self.height - 1) as f32 } else { 0.0 };
+            let level = top + (bottom - top) * t;
+            let row = (y * self.width) as usize;
+            self.light[row..row + self.width as usize].fill(level);
+        }
+    }
+    
+    /// Light level (0.0-1.0) at (x, y), or 0.0 for invalid coordinates
+    pub fn light_at(&self, x: u32, y: u32) -> f32 {
         if x < self.width && y < self.height {
+            self.light[(y * self.width + x) as usize]
+        } else {
+            0.0
+        }
+    }
+
+    /// Switch to a new palette and recolor the whole grid with it
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+        self.refresh_pixels();
+    }
+    
+    /// Recompute every pixel from its cell's state, then paint species colors and the
+    /// `render_mode` overlay. Also undoes anything drawn with `set_pixel`.
+    pub fn refresh_pixels(&mut self) {
+        for (pixel, cell) in self.pixels.iter_mut().zip(&self.cells) {
+            *pixel = self.palette.color(cell.state);
+        }
+        self.paint_overlays();
+    }
+    
+    /// Paint species colors, eye directions and the `render_mode` overlay over the cell colors
+    fn paint_overlays(&mut self) {
+        if self.color_by_species || self.color_by_owner {
+            self.paint_species_colors();
+        }
+        if self.show_eye_direction {
+            self.paint_eye_directions();
+        }
+        self.paint_render_mode();
+    }
+    
+    /// Tint every eye cell with the color for the way it looks
+    fn paint_eye_directions(&mut self) {
+        for (x, y, direction) in self.eye_overlays() {
             let idx = (y * self.width + x) as usize;
-            self.cells[idx] = Cell { state, owner };
-            self.pixels[idx] = state.to_color();
+            if self.cells[idx].state == CellState::Eye {
+                self.pixels[idx] = blend_colors(self.pixels[idx], eye_direction_tint(direction));
+            }
+        }
+    }
+    
+    /// Position and absolute facing of every living organism's eye cells, for renderers
+    /// to draw a direction marker on. Eyes hanging off a non-wrapping grid are left out.
+    ///
+    /// ```
+    /// use simulation::{CellState, Direction, Grid, Organism};
+    ///
+    /// let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+    /// let mut organism = Organism::new(0, 10, 10);
+    /// organism.add_cell(CellState::Eye, 0, -1); // New eyes look up
+    /// grid.add_organism(organism);
+    /// assert_eq!(grid.eye_overlays(), vec![(10, 9, Direction::Up)]);
+    ///
+    /// // Turning the organism turns the eye with it
+    /// for rotation in [Direction::Right, Direction::Down, Direction::Left] {
+    ///     grid.organisms[0].rotation = rotation;
+    ///     assert_eq!(grid.eye_overlays()[0].2, rotation);
+    /// }
+    /// ```
+    pub fn eye_overlays(&self) -> Vec<(u32, u32, Direction)> {
+        let mut overlays = Vec::new();
+        for org in self.organisms.iter().filter(|org| org.is_alive) {
+            for cell in org.cells.iter().filter(|cell| cell.state == CellState::Eye) {
+                let position = self.cell_position(org, cell);
+                if let (Some((x, y)), Some(direction)) = (position, cell.get_absolute_direction(org.rotation)) {
+                    overlays.push((x, y, direction));
+                }
+            }
+        }
+        overlays
+    }
+    
+    /// Switch what the pixels show and repaint the whole grid for it.
+    ///
+    /// ```
+    /// use simulation::{Grid, RenderMode};
+    ///
+    /// let mut grid = Grid::new_seeded(30, 30, 1).unwrap();
+    /// grid.food_production_prob = 0.0;
+    /// grid.create_basic_organism(10, 10);
+    /// grid.create_basic_organism(20, 20);
+    /// let normal = grid.get_pixel(10, 10);
+    /// assert_eq!(normal, grid.get_pixel(20, 20));
+    ///
+    /// grid.set_render_mode(RenderMode::OwnerMap);
+    /// grid.step();
+    /// assert_ne!(grid.get_pixel(10, 10), grid.get_pixel(20, 20));
+    ///
+    /// grid.set_render_mode(RenderMode::Normal);
+    /// assert_eq!(grid.get_pixel(10, 10), normal);
+    /// ```
+    pub fn set_render_mode(&mut self, mode: RenderMode) {
+        self.render_mode = mode;
+        self.refresh_pixels();
+    }
+
+    /// Set a cell's state and owner. Positions off the grid are silently ignored;
+    /// use `try_set_cell` to find out about them.
+    pub fn set_cell(&mut self, x: u32, y: u32, state: CellState, owner: Option<usize>) {
+        let _ = self.try_set_cell(x, y, state, owner);
+    }
+    
+    /// Like `set_cell`, but reports a position off the grid instead of ignoring it
+    ///
+    /// ```
+    /// use simulation::{CellState, Grid, OutOfBounds};
+    ///
+    /// let mut grid = Grid::new_seeded(20, 10, 1).unwrap();
+    /// assert_eq!(grid.try_set_cell(19, 9, CellState::Wall, None), Ok(()));
+    /// assert_eq!(
+    ///     grid.try_set_cell(20, 3, CellState::Wall, None),
+    ///     Err(OutOfBounds { x: 20, y: 3, width: 20, height: 10 })
+    /// );
+    /// ```
+    pub fn try_set_cell(&mut self, x: u32, y: u32, state: CellState, owner: Option<usize>) -> Result<(), OutOfBounds> {
+        let idx = self.cell_index(x, y)
+            .ok_or(OutOfBounds { x, y, width: self.width, height: self.height })?;
+        self.write_cell(idx, Cell { state, owner, food_age: 0 });
+        Ok(())
+    }
+    
+    /// Like `set_cell`, but refuses (returning false) to overwrite a cell of a living
+    /// organism unless `owner` is that organism, and to write off the grid
+    ///
+    /// ```
+    /// use simulation::{CellState, Grid};
+    ///
+    /// let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+    /// grid.create_basic_organism(10, 10);
+    /// let id = grid.organisms[0].id;
+    ///
+    /// assert!(!grid.set_cell_checked(10, 10, CellState::Wall, None));
+    /// assert_eq!(grid.get_cell(10, 10).unwrap().owner, Some(id));
+    /// assert!(grid.set_cell_checked(10, 10, CellState::Killer, Some(id)));
+    ///
+    /// assert!(grid.set_cell_checked(3, 3, CellState::Food, None));
+    /// assert!(grid.set_cell_checked(3, 3, CellState::Wall, None));
+    /// assert!(!grid.set_cell_checked(20, 3, CellState::Wall, None));
+    /// ```
+    pub fn set_cell_checked(&mut self, x: u32, y: u32, state: CellState, owner: Option<usize>) -> bool {
+        let current = match self.get_cell(x, y) {
+            Some(cell) => cell.owner,
+            None => return false,
+        };
+        if let Some(current) = current {
+            let alive = self.organisms.iter().any(|org| org.id == current && org.is_alive);
+            if alive && owner != Some(current) {
+                return false;
+            }
+        }
+        self.set_cell(x, y, state, owner);
+        true
+    }
+    
+    /// Replace the cell at `idx`, keeping its pixel, the empty-cell set and the change log in sync
+    fn write_cell(&mut self, idx: usize, cell: Cell) {
+        if self.cells[idx].state != cell.state {
+            self.changes.record(idx, self.cells[idx].state);
+        }
+        self.empty_cells.set(idx, cell.state == CellState::Empty);
+        self.food_cells.set(idx, cell.state == CellState::Food);
+        if self.cells[idx].state == CellState::Hazard {
+            self.hazard_cells -= 1;
+        }
+        if cell.state == CellState::Hazard {
+            self.hazard_cells += 1;
+        }
+        self.pixels[idx] = self.palette.color(cell.state);
+        self.cells[idx] = cell;
+    }
+    
+    /// Cells whose state changed since the last call, as `(x, y, new state)`, for repainting
+    /// only what moved. Covers every change made through `set_cell`, `step`, `add_organism`
+    /// and `reset`; cells that changed and changed back are left out.
+    pub fn take_changes(&mut self) -> Vec<(u32, u32, CellState)> {
+        self.changes.take(&self.cells, self.width)
+    }
+    
+    /// Recompute the empty- and food-cell sets and the hazard count after `cells` was replaced wholesale
+    pub(crate) fn rebuild_cell_sets(&mut self) {
+        self.hazard_cells = self.cells.iter().filter(|cell| cell.state == CellState::Hazard).count();
+        self.empty_cells = CellSet::from_members(
+            self.cells.iter().map(|cell| cell.state == CellState::Empty).collect()
+        );
+        self.food_cells = CellSet::from_members(
+            self.cells.iter().map(|cell| cell.state == CellState::Food).collect()
+        );
+    }
+    
+    /// Number of food cells, counted from the food-cell set under `sparse_food_scan`
+    pub(crate) fn food_cell_count(&self) -> usize {
+        if self.sparse_food_scan {
+            self.food_cells.len()
+        } else {
+            self.cells.iter().filter(|cell| cell.state == CellState::Food).count()
         }
     }
+    
+    /// Number of empty cells on the grid
+    pub fn empty_cell_count(&self) -> usize {
+        self.empty_cells.len()
+    }
 
     /// Get a reference to a cell at the specified coordinates
     pub fn get_cell(&self, x: u32, y: u32) -> Option<&Cell> {
-        if x < self.width && y < self.height {
-            let idx = (y * self.width + x) as usize;
-            Some(&self.cells[idx])
+        self.cell_index(x, y).map(|idx| &self.cells[idx])
+    }
+    
+    /// Index into `cells`/`pixels` for the given coordinates. Coordinates past the
+    /// right or bottom edge wrap around when `wrap_edges` is set and are rejected otherwise.
+    fn cell_index(&self, x: u32, y: u32) -> Option<usize> {
+        if self.wrap_edges {
+            Some(((y % self.height) * self.width + x % self.width) as usize)
+        } else if x < self.width && y < self.height {
+            Some((y * self.width + x) as usize)
+        } else {
+            None
+        }
+    }
+    
+    /// Map possibly out-of-range coordinates onto the grid: wrapped around
+    /// when `wrap_edges` is set, clamped to the nearest edge otherwise.
+    pub fn normalize_coords(&self, x: i32, y: i32) -> (u32, u32) {
+        if self.wrap_edges {
+            (x.rem_euclid(self.width as i32) as u32, y.rem_euclid(self.height as i32) as u32)
+        } else {
+            (x.clamp(0, self.width as i32 - 1) as u32, y.clamp(0, self.height as i32 - 1) as u32)
+        }
+    }
+    
+    /// The neighbour of (x, y) at offset (dx, dy): wrapped around when `wrap_edges`
+    /// is set, `None` when it falls off the grid otherwise.
+    pub fn neighbor(&self, x: u32, y: u32, dx: i32, dy: i32) -> Option<(u32, u32)> {
+        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+        if self.wrap_edges {
+            Some(self.normalize_coords(nx, ny))
+        } else if nx >= 0 && ny >= 0 && nx < self.width as i32 && ny < self.height as i32 {
+            Some((nx as u32, ny as u32))
         } else {
             None
         }
     }
     
+    /// Absolute grid position of an organism's cell: wrapped around when `wrap_edges`
+    /// is set, `None` when it hangs off the grid otherwise.
+    pub fn cell_position(&self, organism: &Organism, cell: &OrganismCell) -> Option<(u32, u32)> {
+        let (dx, dy) = cell.get_rotated_position(organism.rotation);
+        self.neighbor(organism.x, organism.y, dx, dy)
+    }
+    
+    /// The living organism with the highest fitness (food eaten plus kills); the oldest wins ties
+    pub fn most_successful_organism(&self) -> Option<&Organism> {
+        self.organisms.iter()
+            .filter(|org| org.is_alive)
+            .max_by(|a, b| a.fitness().cmp(&b.fitness()).then(b.id.cmp(&a.id)))
+    }
+    
+    /// How many living organisms share each species (anatomy hash)
+    ///
+    /// ```
+    /// use simulation::{CellState, Grid, Organism};
+    ///
+    /// let mut grid = Grid::new_seeded(60, 60, 1).unwrap();
+    /// grid.create_basic_organism(10, 10);
+    /// grid.create_basic_organism(30, 30);
+    /// let mut mover = Organism::new(0, 50, 10);
+    /// mover.add_cell(CellState::Mover, 0, 1);
+    /// grid.add_organism(mover);
+    /// let (a, b) = (grid.organisms[0].species, grid.organisms[2].species);
+    /// assert_ne!(a, b);
+    ///
+    /// let census = grid.species_census();
+    /// assert_eq!(census.len(), 2);
+    /// assert_eq!(census[&a], 2);
+    /// assert_eq!(census[&b], 1);
+    /// ```
+    pub fn species_census(&self) -> HashMap<u64, usize> {
+        let mut census = HashMap::new();
+        for org in self.organisms.iter().filter(|org| org.is_alive) {
+            *census.entry(org.species).or_insert(0) += 1;
+        }
+        census
+    }
+    
+    /// The body of each living species as (state, x, y) offsets, taken from its first organism
+    pub fn species_layouts(&self) -> HashMap<u64, Vec<(CellState, i32, i32)>> {
+        let mut layouts = HashMap::new();
+        for org in self.organisms.iter().filter(|org| org.is_alive) {
+            layouts.entry(org.species).or_insert_with(|| {
+                org.cells.iter().map(|cell| (cell.state, cell.x, cell.y)).collect()
+            });
+        }
+        layouts
+    }
+    
+    /// The organism occupying a cell, if any. A stale owner whose organism is gone gives `None`.
+    pub fn organism_at(&self, x: u32, y: u32) -> Option<&Organism> {
+        let owner = self.get_cell(x, y)?.owner?;
+        self.organisms.iter().find(|org| org.id == owner)
+    }
+    
+    /// Energy fraction (0.0-1.0) of the organism occupying a cell, if any
+    pub fn energy_fraction_at(&self, x: u32, y: u32) -> Option<f32> {
+        self.organism_at(x, y).map(|org| org.energy_fraction())
+    }
+    
+    /// Age fraction (0.0-1.0) of the organism occupying a cell, if any
+    pub fn lifetime_fraction_at(&self, x: u32, y: u32) -> Option<f32> {
+        self.organism_at(x, y).map(|org| org.lifetime_fraction(self.lifespan_multiplier))
+    }
+    
+    /// The organism with this ID followed by its ancestors, nearest first.
+    /// The walk stops at the first ancestor that is no longer in the simulation.
+    pub fn lineage_of(&self, id: usize) -> Vec<usize> {
+        let mut lineage = Vec::new();
+        let mut next = Some(id);
+        while let Some(id) = next {
+            match self.organisms.iter().find(|org| org.id == id) {
+                Some(org) => {
+                    lineage.push(id);
+                    next = org.parent_id;
+                }
+                None => break,
+            }
+        }
+        lineage
+    }
+    
+    /// Number of steps run since the grid was created or last reset
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count
+    }
+    
+    /// Fraction (0.0-1.0) of the cells in the square of `radius` around (x, y) holding food.
+    /// Cells off the grid count as empty.
+    pub fn food_density(&self, x: u32, y: u32, radius: u32) -> f32 {
+        let r = radius as i32;
+        let mut food = 0;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                if self.neighbor(x, y, dx, dy).is_some_and(|(nx, ny)| self.has_food_at(nx, ny)) {
+                    food += 1;
+                }
+            }
+        }
+        food as f32 / ((2 * radius + 1).pow(2)) as f32
+    }
+    
+    /// Direction of the 3x3 block next to (x, y) holding the most food,
+    /// or `None` when no single block has more than the rest
+    fn richest_food_direction(&self, x: u32, y: u32) -> Option<Direction> {
+        let directions = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+        let densities = directions.map(|direction| {
+            let (dx, dy) = direction.to_delta();
+            self.neighbor(x, y, dx * 2, dy * 2)
+                .map_or(0.0, |(qx, qy)| self.food_density(qx, qy, 1))
+        });
+        let best = densities.iter().cloned().fold(0.0, f32::max);
+        let mut richest = directions.iter().zip(densities).filter(|&(_, density)| density == best);
+        match (richest.next(), richest.next()) {
+            (Some((&direction, _)), None) => Some(direction),
+            _ => None,
+        }
+    }
+    
     /// Check if a position is clear (empty or food)
     pub fn is_position_clear(&self, x: u32, y: u32) -> bool {
         if let Some(cell) = self.get_cell(x, y) {
-            cell.state == CellStates::Empty || cell.state == CellStates::Food
+            cell.state == CellState::Empty || cell.state == CellState::Food
         } else {
             false
         }
@@ -157,32 +947,61 @@ impl Grid {
     /// Check if a position has food
     pub fn has_food_at(&self, x: u32, y: u32) -> bool {
         if let Some(cell) = self.get_cell(x, y) {
-            cell.state == CellStates::Food
+            cell.state == CellState::Food
         } else {
             false
         }
     }
     
-    /// Add a new organism to the grid
+    /// Add a new organism to the grid. Fails, placing nothing, if the population is at
+    /// `max_organisms` or any of its cells would be off the grid (unless edges wrap) or
+    /// on something other than empty space or food.
+    ///
+    /// ```
+    /// use simulation::{CellState, Grid, Organism};
+    ///
+    /// let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+    /// let mut organism = Organism::new(0, 19, 10);
+    /// organism.add_cell(CellState::Producer, -1, 0);
+    /// organism.add_cell(CellState::Producer, 1, 0); // One column past the right edge
+    /// assert!(!grid.add_organism(organism));
+    /// assert!(grid.organisms.is_empty());
+    /// assert!(grid.cells.iter().all(|cell| cell.state == CellState::Empty && cell.owner.is_none()));
+    /// ```
     pub fn add_organism(&mut self, mut organism: Organism) -> bool {
         if self.organisms.len() >= self.max_organisms && self.max_organisms > 0 {
             return false;
         }
         
+        if self.brains_enabled && organism.brain.is_none() {
+            organism.brain = Some(Brain::random(&mut self.rng));
+        }
+        
+        // Lifespan is set by the size at birth; later growth doesn't extend it
+        if organism.max_lifetime == 0 {
+            organism.max_lifetime = organism.max_lifespan(self.lifespan_multiplier);
+        }
+        
+        // Organisms placed from outside have nobody to feed them yet under metabolism
+        if self.metabolism_enabled && organism.parent_id.is_none() {
+            organism.energy = organism.max_energy();
+        }
+        
         // Update organism's ID if not already set
         if organism.id == 0 {
             organism.id = self.next_organism_id;
-            self.next_organism_id += 1;
         }
         
         // More thorough check if all cells can be placed
         let can_place = self.is_position_clear_for_organism(&organism);
         
         if can_place {
-            // Place all cells
+            // Never hand out a placed organism's ID again
+            self.next_organism_id = self.next_organism_id.max(organism.id + 1);
+            
+            // Place all cells; the check above put every one of them on the grid
             for cell in &organism.cells {
-                let (x, y) = organism.get_cell_position(cell);
-                if x < self.width && y < self.height {
+                if let Some((x, y)) = self.cell_position(&organism, cell) {
                     self.set_cell(x, y, cell.state, Some(organism.id));
                 }
             }
@@ -194,42 +1013,47 @@ impl Grid {
         }
     }
     fn is_position_clear_for_organism(&self, organism: &Organism) -> bool {
-        for cell in &organism.cells {
-            let (x, y) = organism.get_cell_position(cell);
-            
-            // Check bounds
-            if x >= self.width || y >= self.height {
-                return false;
-            }
-            
-            // Check cell availability
-            let idx = (y * self.width + x) as usize;
-            let grid_cell = &self.cells[idx];
-            
-            // Check if the cell is occupied by this organism
-            if let Some(owner_id) = grid_cell.owner {
-                if owner_id == organism.id {
-                    continue;
-                }
-            }
-            
-            // Check if the cell is empty
-            if grid_cell.state == CellStates::Empty {
-                continue;
-            }
-            
-            // Check food blocks reproduction rule
-            if grid_cell.state == CellStates::Food && !self.food_blocks_reproduction {
-                continue;
-            }
-            
-            // If we got here, the position is not clear
-            return false;
+        self.bounding_box_fits(organism.bounding_box(), organism.x, organism.y)
+            && self.is_footprint_clear(&organism.footprint(), organism.x, organism.y, organism.id)
+    }
+    
+    /// Whether a body whose `Organism::bounding_box` is `bounds` stays on the grid centered
+    /// on (x, y). Always true when edges wrap. A cheap check before `is_footprint_clear`.
+    ///
+    /// ```
+    /// use simulation::Grid;
+    ///
+    /// let grid = Grid::new_seeded(10, 10, 1).unwrap();
+    /// assert!(grid.bounding_box_fits((-1, -1, 1, 1), 1, 8));
+    /// assert!(!grid.bounding_box_fits((-1, -1, 1, 1), 0, 5));
+    /// assert!(!grid.bounding_box_fits((-1, -1, 1, 1), 5, 9));
+    /// ```
+    pub fn bounding_box_fits(&self, bounds: (i32, i32, i32, i32), x: u32, y: u32) -> bool {
+        if self.wrap_edges {
+            return true;
         }
-        
-        return true;
+        let (min_x, min_y, max_x, max_y) = bounds;
+        let (x, y) = (x as i64, y as i64);
+        x + min_x as i64 >= 0 && y + min_y as i64 >= 0
+            && x + (max_x as i64) < self.width as i64 && y + (max_y as i64) < self.height as i64
+    }
+    
+    /// Whether a body with cell offsets `footprint` (see `Organism::footprint`) fits centered
+    /// on (x, y): every cell on the grid and empty, food unless `food_blocks_reproduction`,
+    /// or already owned by organism `id`. Stops at the first blocked cell.
+    pub fn is_footprint_clear(&self, footprint: &[(i32, i32)], x: u32, y: u32, id: usize) -> bool {
+        footprint.iter().all(|&(dx, dy)| {
+            let idx = match self.neighbor(x, y, dx, dy) {
+                Some((cx, cy)) => (cy * self.width + cx) as usize,
+                None => return false, // Off the grid
+            };
+            let cell = &self.cells[idx];
+            cell.owner == Some(id)
+                || cell.state == CellState::Empty
+                || (cell.state == CellState::Food && !self.food_blocks_reproduction)
+        })
     }
-    /// Create a new basic organism at a position
+    /// Create a new organism at a position, with the default anatomy if one is set
     pub fn create_basic_organism(&mut self, x: u32, y: u32) -> bool {
         if x >= self.width || y >= self.height {
             return false;
@@ -238,78 +1062,114 @@ impl Grid {
         // Create a new organism - use x and y from the parameters
         let mut organism = Organism::new(self.next_organism_id, x, y);
         
-        // Add some basic cells to the organism object
-        organism.add_cell(CellStates::Mouth, 0, 0); // Center
-        organism.add_cell(CellStates::Producer, 1, 1); // Up Right
-        organism.add_cell(CellStates::Producer, -1, -1); // Down Left
-        
-        // Add the organism to the grid
-        self.add_organism(organism)
-    }
-    
-    /// Remove an organism from the grid
-    fn remove_organism(&mut self, org_id: usize) {
-        if let Some(index) = self.organisms.iter().position(|org| org.id == org_id) {
-            // First, collect all the cells to convert to food
-            let mut cells_to_food = Vec::new();
-            {
-                let organism = &self.organisms[index];
-                for cell in &organism.cells {
-                    let (x, y) = organism.get_cell_position(cell);
-                    if x < self.width && y < self.height {
-                        cells_to_food.push((x, y));
-                    }
+        match &self.default_anatomy {
+            Some(anatomy) => {
+                organism.cells.clear();
+                for &(state, cell_x, cell_y) in anatomy {
+                    organism.add_cell(state, cell_x, cell_y);
                 }
             }
-            
-            // Now turn those cells into food
-            for (x, y) in cells_to_food {
-                self.set_cell(x, y, CellStates::Food, None);
+            None => {
+                // Add some basic cells around the center mouth placed by Organism::new
+                organism.add_cell(CellState::Producer, 1, 1); // Up Right
+                organism.add_cell(CellState::Producer, -1, -1); // Down Left
             }
-            
-            // Remove the organism
-            self.organisms.remove(index);
         }
+        organism.energy = organism.max_energy();
+        
+        // Add the organism to the grid
+        self.add_organism(organism)
     }
     
-    /// Remove dead organisms
+    /// Remove dead organisms in one sweep, turning their cells into food
     fn remove_dead_organisms(&mut self) {
-        let dead_ids: Vec<usize> = self.organisms.iter()
-            .filter(|org| !org.is_alive)
-            .map(|org| org.id)
-            .collect();
-            
-        for id in dead_ids {
-            self.remove_organism(id);
+        let mut cells_to_food = Vec::new();
+        for org in self.organisms.iter().filter(|org| !org.is_alive) {
+            let (id, cause) = (org.id, org.death_cause.unwrap_or(DeathCause::Killed));
+            match cause {
+                DeathCause::OldAge => self.sim_stats.deaths_old_age += 1,
+                DeathCause::Killed => self.sim_stats.deaths_killed += 1,
+                DeathCause::Starved => self.sim_stats.deaths_starved += 1,
+            }
+            self.death_stats.record(cause);
+            self.recent_deaths.push((id, cause));
+            self.on_event.emit(GridEvent::OrganismDied { id, cause });
+            cells_to_food.extend(org.cells.iter().filter_map(|cell| self.cell_position(org, cell)));
         }
+        
+        for (x, y) in cells_to_food {
+            self.set_cell(x, y, CellState::Food, None);
+        }
+        self.organisms.retain(|org| org.is_alive);
     }
 
-    /// Try to produce food in adjacent empty cells
-    fn try_produce_food(&self, x: u32, y: u32, new_cells: &mut [Cell]) {
-        // Define adjacent cells (up, down, left, right)
-        let adjacent = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+    /// Roll for food in each empty cell next to a producer at (x, y), collecting the
+    /// positions that came up. Food isn't placed here so the caller can charge for it first.
+    fn produce_food_around(&mut self, x: u32, y: u32, produced: &mut Vec<(u32, u32)>) {
+        // Use food_production_prob value (scale from 0-100 to 0-1), dimmed by the light here
+        let prob = self.food_production_prob / 100.0 * self.light_at(x, y);
         
-        for (dx, dy) in adjacent.iter() {
-            let nx = x as i32 + dx;
-            let ny = y as i32 + dy;
+        for (dx, dy) in self.neighbor_offsets() {
+            let (nx, ny) = match self.neighbor(x, y, *dx, *dy) {
+                Some(pos) => pos,
+                None => continue, // Off the grid
+            };
             
-            // Check bounds
-            if nx >= 0 && ny >= 0 && nx < self.width as i32 && ny < self.height as i32 {
-                let nidx = (ny as u32 * self.width + nx as u32) as usize;
-                
-                // Only produce food in empty cells with some probability
-                if self.cells[nidx].state == CellStates::Empty && random::<f32>() < 0.1 {
-                    new_cells[nidx].state = CellStates::Food;
-                }
+            let idx = (ny * self.width + nx) as usize;
+            if self.cells[idx].state == CellState::Empty && self.rng.gen::<f32>() < prob {
+                produced.push((nx, ny));
             }
         }
     }
     
-    /// Process killer cells damaging other organisms
+    /// Offsets of the cells that eat, kill and produce into: the 4 orthogonal neighbours,
+    /// plus the diagonals when `use_diagonal_neighbors` is set
+    fn neighbor_offsets(&self) -> &'static [(i32, i32)] {
+        const ORTHOGONAL: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+        const WITH_DIAGONALS: [(i32, i32); 8] = [(0, 1), (1, 0), (0, -1), (-1, 0),
+                                                 (1, 1), (1, -1), (-1, 1), (-1, -1)];
+        if self.use_diagonal_neighbors {
+            &WITH_DIAGONALS
+        } else {
+            &ORTHOGONAL
+        }
+    }
+    
+    /// Whether the cell at (x, y), hit from direction (dx, dy), has an armor cell of the
+    /// same organism on either side of the contacted edge. A diagonal hit touches a corner,
+    /// which is shielded by the two cells between the killer and the target.
+    fn edge_shielded(&self, x: u32, y: u32, dx: i32, dy: i32, owner: usize) -> bool {
+        let sides = if dx != 0 && dy != 0 {
+            [(-dx, 0), (0, -dy)]
+        } else {
+            [(dy, dx), (-dy, -dx)]
+        };
+        sides.iter().any(|&(px, py)| {
+            self.neighbor(x, y, px, py)
+                .and_then(|(sx, sy)| self.get_cell(sx, sy))
+                .is_some_and(|cell| cell.owner == Some(owner) && cell.state == CellState::Armor)
+        })
+    }
+    
+    /// Damage a killer or hazard touching the organism cell at (x, y) deals, with (dx, dy)
+    /// the step from the attacking cell to it; armor soaks up part of the hit
+    fn hit_damage(&self, x: u32, y: u32, dx: i32, dy: i32, target_id: usize, target_state: CellState) -> u32 {
+        if target_state == CellState::Armor {
+            self.killer_damage.saturating_sub(self.armor_reduction)
+        } else if self.armor_blocks_adjacent && self.edge_shielded(x, y, dx, dy, target_id) {
+            0
+        } else {
+            self.killer_damage
+        }
+    }
+    
+    /// Process killer cells, and hazard cells, damaging organisms
     fn process_killer_cells(&mut self) {
-        // Track which organisms take damage and which killer cells hit another killer
-        let mut damage_map: std::collections::HashMap<usize, u32> = std::collections::HashMap::new();
-        let mut killer_hit_map: std::collections::HashMap<usize, bool> = std::collections::HashMap::new();
+        // Track the hits (attacker, damage) each organism takes, and which killer
+        // hit another killer (attacker -> killer it hit). Hazards hit with no attacker.
+        // Ordered maps so damage is applied in the same order on every run.
+        let mut damage_map: BTreeMap<usize, Vec<(Option<usize>, u32)>> = BTreeMap::new();
+        let mut killer_hit_map: BTreeMap<usize, usize> = BTreeMap::new();
         
         // Check each organism's killer cells
         for org in &self.organisms {
@@ -318,27 +1178,34 @@ impl Grid {
             }
             
             for cell in &org.cells {
-                if cell.state != CellStates::Killer {
+                if cell.state != CellState::Killer {
                     continue;
                 }
                 
-                let (cx, cy) = org.get_cell_position(cell);
-                let adjacents = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-                
-                for (dx, dy) in adjacents.iter() {
-                    let nx = (cx as i32 + dx).max(0).min(self.width as i32 - 1) as u32;
-                    let ny = (cy as i32 + dy).max(0).min(self.height as i32 - 1) as u32;
+                let (cx, cy) = match self.cell_position(org, cell) {
+                    Some(pos) => pos,
+                    None => continue, // Off the grid
+                };
+                for (dx, dy) in self.neighbor_offsets() {
+                    let (nx, ny) = match self.neighbor(cx, cy, *dx, *dy) {
+                        Some(pos) => pos,
+                        None => continue, // Off the grid
+                    };
                     
                     if let Some(target_cell) = self.get_cell(nx, ny) {
-                        // If cell belongs to another organism and is not armor
+                        // If cell belongs to another organism
                         if let Some(target_id) = target_cell.owner {
-                            if target_id != org.id && target_cell.state != CellStates::Armor {
+                            if target_id != org.id {
+                                let damage = self.hit_damage(nx, ny, *dx, *dy, target_id, target_cell.state);
+                                
                                 // Track damage
-                                *damage_map.entry(target_id).or_insert(0) += 1;
+                                if damage > 0 {
+                                    damage_map.entry(target_id).or_default().push((Some(org.id), damage));
+                                }
                                 
                                 // Track if this killer hit another killer (for mutual kill)
-                                if target_cell.state == CellStates::Killer {
-                                    killer_hit_map.insert(org.id, true);
+                                if target_cell.state == CellState::Killer {
+                                    killer_hit_map.insert(org.id, target_id);
                                 }
                             }
                         }
@@ -347,71 +1214,99 @@ impl Grid {
             }
         }
         
-        // Apply damage to organisms
-        for (org_id, damage) in damage_map {
-            if let Some(index) = self.organisms.iter().position(|org| org.id == org_id) {
-                if self.insta_kill {
-                    self.organisms[index].is_alive = false;
-                } else {
-                    for _ in 0..damage {
-                        self.organisms[index].harm();
+        // Hazards hurt every organism cell next to them, like a killer no one owns
+        let hazard_targets = if self.hazard_cells > 0 { self.organisms.as_slice() } else { &[] };
+        for org in hazard_targets.iter().filter(|org| org.is_alive) {
+            for cell in &org.cells {
+                let (cx, cy) = match self.cell_position(org, cell) {
+                    Some(pos) => pos,
+                    None => continue, // Off the grid
+                };
+                for (dx, dy) in self.neighbor_offsets() {
+                    let hazard = self.neighbor(cx, cy, *dx, *dy)
+                        .and_then(|(nx, ny)| self.get_cell(nx, ny))
+                        .is_some_and(|neighbor| neighbor.state == CellState::Hazard);
+                    if hazard {
+                        let damage = self.hit_damage(cx, cy, -dx, -dy, org.id, cell.state);
+                        if damage > 0 {
+                            damage_map.entry(org.id).or_default().push((None, damage));
+                        }
                     }
                 }
             }
         }
         
-        // Apply mutual killer damage if insta_kill is enabled
-        if self.insta_kill {
-            for (org_id, _) in killer_hit_map {
-                if let Some(index) = self.organisms.iter().position(|org| org.id == org_id) {
+        // Apply damage to organisms, remembering who dealt each lethal blow
+        let mut kills: Vec<usize> = Vec::new();
+        for (org_id, hits) in damage_map {
+            if let Some(index) = self.organisms.iter().position(|org| org.id == org_id) {
+                if !self.organisms[index].is_alive {
+                    continue;
+                }
+                if self.insta_kill {
+                    self.organisms[index].die(DeathCause::Killed);
+                    kills.extend(hits[0].0);
+                    continue;
+                }
+                'hits: for (attacker_id, damage) in hits {
+                    for _ in 0..damage {
+                        self.organisms[index].harm();
+                        if !self.organisms[index].is_alive {
+                            kills.extend(attacker_id);
+                            break 'hits;
+                        }
+                    }
+                }
+            }
+        }
+        
+        // Apply mutual killer damage if insta_kill is enabled
+        if self.insta_kill {
+            for (org_id, hit_id) in killer_hit_map {
+                if let Some(index) = self.organisms.iter().position(|org| org.id == org_id) {
+                    let was_alive = self.organisms[index].is_alive;
                     self.organisms[index].harm();
+                    if was_alive && !self.organisms[index].is_alive {
+                        kills.push(hit_id);
+                    }
                 }
             }
         }
+        
+        for attacker_id in kills {
+            if let Some(attacker) = self.organisms.iter_mut().find(|org| org.id == attacker_id) {
+                attacker.kills += 1;
+            }
+        }
     }
         
     // Fix for process_reproduction function
     // Updated process_reproduction function to avoid borrowing conflict
+    // Note: the path is always traced as a straight line inside the grid, even
+    // when `wrap_edges` is set, so births never reach across the wrapped edge.
     fn is_straight_path_clear(&self, x1: u32, y1: u32, x2: u32, y2: u32) -> bool {
         // If the points are the same, path is clear
         if x1 == x2 && y1 == y2 {
             return true;
         }
     
-        // Allow diagonal paths by using Bresenham's line algorithm
-        let dx = (x2 as i32 - x1 as i32).abs();
-        let dy = (y2 as i32 - y1 as i32).abs();
-        let sx = if x1 < x2 { 1 } else { -1 };
-        let sy = if y1 < y2 { 1 } else { -1 };
-        
-        let mut err = dx - dy;
-        let mut x = x1 as i32;
-        let mut y = y1 as i32;
-        
-        while x != x2 as i32 || y != y2 as i32 {
-            // Skip checking the start and end positions
-            if (x != x1 as i32 || y != y1 as i32) && (x != x2 as i32 || y != y2 as i32) {
-                if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
-                    return false;  // Out of bounds
-                }
-                
-                if !self.is_position_clear(x as u32, y as u32) {
-                    return false;  // Path blocked
-                }
+        // Allow diagonal paths by using Bresenham's line algorithm,
+        // skipping the start and end positions
+        let end = (x2 as i32, y2 as i32);
+        for (x, y) in BresenhamLine::new(x1 as i32, y1 as i32, x2 as i32, y2 as i32).skip(1) {
+            if (x, y) == end {
+                break;
             }
-            
-            let e2 = 2 * err;
-            if e2 > -dy {
-                err -= dy;
-                x += sx;
+            if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+                return false;  // Out of bounds
             }
-            if e2 < dx {
-                err += dx;
-                y += sy;
+            
+            if !self.is_position_clear(x as u32, y as u32) {
+                return false;  // Path blocked
             }
         }
         
-        return true;  // Path is clear
+        true  // Path is clear
     }
 
     // Also add this debug function to help diagnose reproduction issues
@@ -424,7 +1319,7 @@ impl Grid {
                 "Organism {}: food={}/{}, cells={}, alive={}",
                 i, 
                 org.food_collected, 
-                org.food_needed_to_reproduce(),
+                org.food_needed_to_reproduce(self.extra_mover_food_cost, self.reproduction_cost_multiplier),
                 org.cells.len(),
                 org.is_alive
             );
@@ -445,6 +1340,16 @@ impl Grid {
                 continue;
             }
             
+            // Organisms over the size cap don't get to pass on their body
+            if self.max_organism_cells > 0 && self.organisms[i].cells.len() > self.max_organism_cells {
+                continue;
+            }
+            
+            // Still cooling down from the last birth; food keeps for later
+            if self.organisms[i].steps_since_reproduction < self.reproduction_cooldown {
+                continue;
+            }
+            
             reproduction_candidates.push(i);
         }
         
@@ -452,32 +1357,43 @@ impl Grid {
         for org_idx in reproduction_candidates {
             // Check if we can add more organisms
             if current_organism_count + new_organisms.len() < max_organisms || max_organisms == 0 {
+                // Near the cap, skip some attempts; the parent keeps its food for a later step
+                let population = current_organism_count + new_organisms.len();
+                let skip = self.skip_probability(population);
+                if skip > 0.0 && self.rng.gen::<f32>() < skip {
+                    continue;
+                }
+                
                 // Get parent organism's position
                 let parent_x = self.organisms[org_idx].x;
                 let parent_y = self.organisms[org_idx].y;
                 
                 // Try to reproduce
-                if let Some(mut offspring) = self.organisms[org_idx].try_reproduce() {
-                    // Set the ID now
+                let max_cells = self.max_organism_cells;
+                if let Some(mut offspring) = self.organisms[org_idx].try_reproduce(
+                    &self.mutation_params, max_cells, self.extra_mover_food_cost,
+                    self.reproduction_cost_multiplier, self.deterministic_placement, &mut self.rng) {
+                    // Checked under the next free ID; the ID is only taken once the child is placed
                     offspring.id = self.next_organism_id;
-                    self.next_organism_id += 1;
                     
-                    // Check for position clearance and straight path
-                    if self.is_position_clear_for_organism(&offspring) && 
+                    // Check for position clearance and straight path, reusing one footprint
+                    // and bounding box for the birth spot and every alternative
+                    let footprint = offspring.footprint();
+                    let bounds = offspring.bounding_box();
+                    if self.bounding_box_fits(bounds, offspring.x, offspring.y) &&
+                    self.is_footprint_clear(&footprint, offspring.x, offspring.y, offspring.id) && 
                     self.is_straight_path_clear(parent_x, parent_y, offspring.x, offspring.y) {
-                        new_organisms.push(offspring);
+                        new_organisms.push((org_idx, offspring));
                     } else {
                         // Try alternative positions
                         let alternative_positions = self.get_alternative_positions(&offspring);
                         for (new_x, new_y) in alternative_positions {
-                            // Create a copy at the new position
-                            let mut alt_offspring = offspring.clone();
-                            alt_offspring.x = new_x;
-                            alt_offspring.y = new_y;
-                            
-                            if self.is_position_clear_for_organism(&alt_offspring) && 
+                            if self.bounding_box_fits(bounds, new_x, new_y) &&
+                            self.is_footprint_clear(&footprint, new_x, new_y, offspring.id) && 
                             self.is_straight_path_clear(parent_x, parent_y, new_x, new_y) {
-                                new_organisms.push(alt_offspring);
+                                offspring.x = new_x;
+                                offspring.y = new_y;
+                                new_organisms.push((org_idx, offspring));
                                 break;
                             }
                         }
@@ -487,11 +1403,43 @@ impl Grid {
         }
         
         // Add all new organisms one by one
-        for org in new_organisms {
-            self.add_organism(org);
+        for (org_idx, mut org) in new_organisms {
+            // Under metabolism the parent hands half its energy to the child, once it's placed
+            let energy_share = if self.metabolism_enabled { self.organisms[org_idx].energy / 2 } else { 0 };
+            if self.metabolism_enabled {
+                org.energy = energy_share;
+            }
+            org.id = self.next_organism_id;
+            let (id, parent_id) = (org.id, org.parent_id);
+            if self.add_organism(org) {
+                let parent = &mut self.organisms[org_idx];
+                parent.energy -= energy_share;
+                parent.record_birth(self.extra_mover_food_cost, self.reproduction_cost_multiplier);
+                self.sim_stats.births += 1;
+                if let Some(parent_id) = parent_id {
+                    self.on_event.emit(GridEvent::OrganismBorn { id, parent_id });
+                }
+            }
+        }
+    }
+
+    /// Chance a reproduction attempt is skipped at this population: 0 up to
+    /// `soft_cap_fraction` of `max_organisms`, rising linearly to 1 at the cap
+    fn skip_probability(&self, population: usize) -> f32 {
+        if self.max_organisms == 0 || self.soft_cap_fraction >= 1.0 {
+            return 0.0;
+        }
+        let soft_cap = self.soft_cap_fraction.max(0.0) * self.max_organisms as f32;
+        let over = population as f32 - soft_cap;
+        if over <= 0.0 {
+            0.0
+        } else {
+            over / (self.max_organisms as f32 - soft_cap)
         }
     }
 
+    /// Fallback birth spots, nearest shell first. The order only depends on the
+    /// organism's position, so `deterministic_placement` can rely on it.
     fn get_alternative_positions(&self, organism: &Organism) -> Vec<(u32, u32)> {
         let mut positions = Vec::new();
         let base_x = organism.x;
@@ -499,8 +1447,7 @@ impl Grid {
         
         // Try different offsets in a more comprehensive pattern
         for distance in 1..15 {  // Try a larger range of distances
-            // Cast distance to i32 for the ranges
-            let distance_i32 = distance as i32;
+            let distance_i32: i32 = distance;
             
             // Try more directions at each distance
             for dx in -distance_i32..=distance_i32 {
@@ -527,8 +1474,19 @@ impl Grid {
         positions
     }
         
-    // Function with fixed borrowing in process_eating method 
-    fn process_eating(&mut self) {
+    /// Whether any of the organism's mouths has food next to it
+    fn mouth_near_food(&self, org: &Organism) -> bool {
+        org.cells.iter()
+            .filter(|cell| cell.state == CellState::Mouth)
+            .filter_map(|cell| self.cell_position(org, cell))
+            .any(|(cx, cy)| self.neighbor_offsets().iter().any(|(dx, dy)| {
+                self.neighbor(cx, cy, *dx, *dy).is_some_and(|(nx, ny)| self.has_food_at(nx, ny))
+            }))
+    }
+        
+    // Function with fixed borrowing in process_eating method.
+    // Returns the food each organism ate, by index in `organisms`.
+    fn process_eating(&mut self) -> Vec<u32> {
         // First collect all eating actions to avoid borrowing conflicts
         let mut food_eaten = Vec::new();
         let mut org_food_collected = Vec::new();
@@ -540,19 +1498,22 @@ impl Grid {
             }
             
             for cell in &org.cells {
-                if cell.state != CellStates::Mouth {
+                if cell.state != CellState::Mouth {
                     continue;
                 }
                 
-                let (cx, cy) = org.get_cell_position(cell);
-                let adjacents = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-                
-                for (dx, dy) in adjacents.iter() {
-                    let nx = (cx as i32 + dx).max(0).min(self.width as i32 - 1) as u32;
-                    let ny = (cy as i32 + dy).max(0).min(self.height as i32 - 1) as u32;
+                let (cx, cy) = match self.cell_position(org, cell) {
+                    Some(pos) => pos,
+                    None => continue, // Off the grid
+                };
+                for (dx, dy) in self.neighbor_offsets() {
+                    let (nx, ny) = match self.neighbor(cx, cy, *dx, *dy) {
+                        Some(pos) => pos,
+                        None => continue, // Off the grid
+                    };
                     
-                    // If there's food 
-                    if self.has_food_at(nx, ny) {
+                    // If there's food no other mouth has claimed this step
+                    if self.has_food_at(nx, ny) && !food_eaten.contains(&(nx, ny)) {
                         food_eaten.push((nx, ny));
                         org_food_collected.push(org_idx);
                         // Break after finding one piece of food to match JS behavior
@@ -564,25 +1525,77 @@ impl Grid {
         }
         
         // Apply food collection to organisms
+        let mut meals = vec![0; self.organisms.len()];
         for org_idx in org_food_collected {
+            meals[org_idx] += 1;
             self.organisms[org_idx].food_collected += 1;
+            self.organisms[org_idx].total_food_eaten += 1;
+            if self.metabolism_enabled {
+                self.organisms[org_idx].regenerate_energy(self.food_energy);
+            }
         }
         
         // Remove all eaten food
         for (x, y) in food_eaten {
-            self.set_cell(x, y, CellStates::Empty, None);
+            self.set_cell(x, y, CellState::Empty, None);
+            self.on_event.emit(GridEvent::FoodEaten { x, y });
         }
+        
+        meals
     }
     
 
     // Fixed update_organisms method to resolve borrowing issues
     fn update_organisms(&mut self) {
         // Process eating
-        self.process_eating();
+        let meals = self.process_eating();
         
         // Process killer cells
         self.process_killer_cells();
         
+        // Let eyed movers look around while every organism is still on the grid.
+        // Any cell of an organism carrying killer cells is seen as a threat.
+        let predators: std::collections::HashSet<usize> = self.organisms.iter()
+            .filter(|org| org.is_alive && org.has_killers())
+            .map(|org| org.id)
+            .collect();
+        let sees_at = |x: u32, y: u32| -> CellState {
+            // Eyes never look across a wrapped edge; the grid border reads as a wall
+            if x >= self.width || y >= self.height {
+                return CellState::Wall;
+            }
+            match self.get_cell(x, y) {
+                Some(cell) if cell.owner.is_some_and(|owner| predators.contains(&owner)) => CellState::Killer,
+                Some(cell) => cell.state,
+                None => CellState::Wall,
+            }
+        };
+        // Eyeless movers smell how much food is around them instead, and movers paying food
+        // to move can only go hungry away from food
+        let smell_area = ((2 * self.sense_radius + 1).pow(2) - 1) as f32;
+        let senses: Vec<OrganismSenses> = self.organisms.iter()
+            .map(|org| {
+                let eyeless_mover = org.is_alive && org.has_movers() && !org.has_eyes();
+                OrganismSenses {
+                    sighting: if org.is_alive && org.has_movers() && org.has_eyes() {
+                        org.look(self.eye_view_distance, sees_at)
+                    } else {
+                        None
+                    },
+                    smell: (eyeless_mover && self.sense_radius > 0).then(|| {
+                        let food = org.food_in_radius(self.sense_radius, |x, y| self.has_food_at(x, y));
+                        food as f32 / smell_area
+                    }),
+                    touch_dir: if eyeless_mover && self.food_gradient_movement {
+                        self.richest_food_direction(org.x, org.y)
+                    } else {
+                        None
+                    },
+                    touching: self.move_food_cost > 0.0 && org.is_alive && self.mouth_near_food(org),
+                }
+            })
+            .collect();
+        
         // First clear all organisms from the grid
         {
             let mut cells_to_clear = Vec::new();
@@ -593,8 +1606,7 @@ impl Grid {
                 }
                 
                 for cell in &org.cells {
-                    let (x, y) = org.get_cell_position(cell);
-                    if x < self.width && y < self.height {
+                    if let Some((x, y)) = self.cell_position(org, cell) {
                         cells_to_clear.push((x, y, org.id));
                     }
                 }
@@ -604,52 +1616,86 @@ impl Grid {
             for (x, y, org_id) in cells_to_clear {
                 let idx = (y * self.width + x) as usize;
                 if self.cells[idx].owner == Some(org_id) {
-                    self.cells[idx] = Cell { state: CellStates::Empty, owner: None };
+                    self.write_cell(idx, Cell { state: CellState::Empty, owner: None, food_age: 0 });
                 }
             }
         }
         
-        // Update organisms
-        let mut updated_organisms = Vec::new();
+        // Update organisms in place. Only `cells` is read while `organisms` is mutated,
+        // so the borrows split by field without cloning every organism.
         let width = self.width;
         let height = self.height;
+        let cells = &self.cells;
         
-        for org in &self.organisms {
+        // Each organism draws from its own stream of this step's seed, so the result
+        // doesn't depend on the order organisms are updated in
+        let step_seed: u64 = self.rng.gen();
+        
+        let update = |(index, org): (usize, &mut Organism),
+                      senses: OrganismSenses|
+                      -> Option<OrganismAction> {
             if !org.is_alive {
-                updated_organisms.push(org.clone());
-                continue;
+                return None;
             }
             
-            // Clone the organism for the update
-            let mut updated_org = org.clone();
+            if self.metabolism_enabled {
+                org.metabolize(self.cell_upkeep);
+                if !org.is_alive {
+                    return None;
+                }
+            } else {
+                org.regenerate_energy(self.energy_regen);
+            }
             
-            // Save the grid state for checking clear positions
-            let is_position_clear = |x: u32, y: u32| -> bool {
-                if x >= width || y >= height {
-                    return false;
+            // A mover stuck without food to pay for moving starves unless food reaches its mouth
+            if org.has_movers() && !senses.touching && !org.can_afford_move(self.move_food_cost) {
+                org.hunger += 1;
+                if org.hunger >= self.starvation_steps {
+                    org.die(DeathCause::Starved);
+                    return None;
                 }
-                let idx = (y * width + x) as usize;
-                let cell = &self.cells[idx];
-                cell.state == CellStates::Empty || cell.state == CellStates::Food
-            };
+            } else {
+                org.hunger = 0;
+            }
             
-            let has_food_at = |x: u32, y: u32| -> bool {
+            // Check clear positions against the grid with every organism lifted off it
+            let is_position_clear = |x: u32, y: u32| -> bool {
                 if x >= width || y >= height {
                     return false;
                 }
                 let idx = (y * width + x) as usize;
-                let cell = &self.cells[idx];
-                cell.state == CellStates::Food
+                let cell = &cells[idx];
+                cell.state == CellState::Empty || cell.state == CellState::Food
             };
             
-            // Update the organism with the closures
-            updated_org.update(width, height, is_position_clear, has_food_at, self.lifespan_multiplier);
-
-            updated_organisms.push(updated_org);
-        }
+            org.heal(self.heal_interval);
+            
+            let mut rng = ChaCha8Rng::seed_from_u64(step_seed);
+            rng.set_stream(index as u64);
+            
+            // Update the organism with the closure (eating is handled by process_eating)
+            let action = org.update(width, height, self.wrap_edges, is_position_clear, self.lifespan_multiplier,
+                                    self.move_energy_cost, self.move_food_cost, senses.sighting, senses.smell,
+                                    senses.touch_dir, &mut rng);
+            Some(action)
+        };
         
-        // Replace the old organisms with the updated ones
-        self.organisms = updated_organisms;
+        #[cfg(feature = "rayon")]
+        let actions: Vec<Option<OrganismAction>> = {
+            use rayon::prelude::*;
+            self.organisms.par_iter_mut().enumerate().zip(senses).map(|(org, senses)| update(org, senses)).collect()
+        };
+        #[cfg(not(feature = "rayon"))]
+        let actions: Vec<Option<OrganismAction>> =
+            self.organisms.iter_mut().enumerate().zip(senses).map(|(org, senses)| update(org, senses)).collect();
+        
+        // Eating counts as the step's action only when the organism did nothing else
+        self.recent_actions = self.organisms.iter().zip(actions).zip(meals)
+            .filter_map(|((org, action), count)| match action? {
+                OrganismAction::Idle if count > 0 => Some((org.id, OrganismAction::Ate { count })),
+                action => Some((org.id, action)),
+            })
+            .collect();
         
         // Re-place all organisms on the grid
         let mut cells_to_set = Vec::new();
@@ -659,8 +1705,7 @@ impl Grid {
             }
             
             for cell in &org.cells {
-                let (x, y) = org.get_cell_position(cell);
-                if x < self.width && y < self.height {
+                if let Some((x, y)) = self.cell_position(org, cell) {
                     cells_to_set.push((x, y, cell.state, org.id));
                 }
             }
@@ -678,32 +1723,104 @@ impl Grid {
         self.remove_dead_organisms();
     }
 
-        /// Main step function to update the entire simulation
+        /// Main step function to update the entire simulation.
+        /// Organisms that died during the step are removed at its end, their cells turning into food.
+        ///
+        /// ```
+        /// use simulation::{CellState, DeathCause, Grid};
+        ///
+        /// let mut grid = Grid::new_seeded(100, 100, 1).unwrap();
+        /// grid.food_production_prob = 0.0;
+        /// for i in 0..100 {
+        ///     grid.create_basic_organism(5 + (i % 10) * 10, 5 + (i / 10) * 10);
+        /// }
+        /// let bodies: Vec<(u32, u32)> = grid.organisms.iter()
+        ///     .flat_map(|org| org.cells.iter().filter_map(|cell| grid.cell_position(org, cell)))
+        ///     .collect();
+        /// for org in &mut grid.organisms {
+        ///     org.die(DeathCause::Killed);
+        /// }
+        /// grid.step();
+        /// assert!(grid.organisms.is_empty());
+        /// assert_eq!(bodies.len(), 300);
+        /// assert!(bodies.iter().all(|&(x, y)| grid.get_cell(x, y).unwrap().state == CellState::Food));
+        /// ```
         pub fn step(&mut self) {
+            self.simulate_step();
+            // Pixels follow cell states through set_cell; only species colors and overlays need repainting
+            self.paint_overlays();
+        }
+        
+        /// Run `n` steps, repainting pixels once at the end instead of after every step.
+        /// Leaves the grid, pixels included, just as `n` calls to `step` would.
+        ///
+        /// ```
+        /// use simulation::Grid;
+        ///
+        /// let run = |batched: bool| {
+        ///     let mut grid = Grid::new_seeded(40, 40, 7).unwrap();
+        ///     grid.color_by_owner = true;
+        ///     grid.origin_of_life();
+        ///     if batched {
+        ///         grid.step_n(5);
+        ///     } else {
+        ///         for _ in 0..5 {
+        ///             grid.step();
+        ///         }
+        ///     }
+        ///     grid.pixels
+        /// };
+        /// assert_eq!(run(true), run(false));
+        /// ```
+        pub fn step_n(&mut self, n: u32) {
+            if n == 0 {
+                return;
+            }
+            for _ in 0..n {
+                self.simulate_step();
+            }
+            self.refresh_pixels();
+        }
+        
+        /// Everything a step does except painting species colors and overlays
+        fn simulate_step(&mut self) {
+            self.tick_count += 1;
+            self.begin_step_stats();
+            self.recent_deaths.clear();
+            
             // Update organisms
             self.update_organisms();
             
+            // Let old food spoil before any new food is added
+            if let Some(lifespan) = self.food_lifespan {
+                self.spoil_food(lifespan);
+            }
+            
             // Randomly produce food in empty cells based on food_drop_prob
             if self.food_drop_prob > 0.0 {
                 let total_cells = (self.width * self.height) as f32;
                 let food_to_generate = ((total_cells * self.food_drop_prob) / 50000.0).max(1.0) as u32;
         
+                let total_cells = self.cells.len();
                 for _ in 0..food_to_generate {
-                    if random::<f32>() <= self.food_drop_prob {
-                        let x = (random::<f32>() * self.width as f32) as u32;
-                        let y = (random::<f32>() * self.height as f32) as u32;
-                        
-                        let idx = (y * self.width + x) as usize;
-                        if self.cells[idx].state == CellStates::Empty {
-                            self.set_cell(x, y, CellStates::Food, None);
+                    if self.rng.gen::<f32>() <= self.food_drop_prob {
+                        // Same odds as dropping on a random cell and keeping it only if empty,
+                        // without scanning for empty cells
+                        if self.rng.gen_range(0..total_cells) < self.empty_cells.len() {
+                            let nth = self.rng.gen_range(0..self.empty_cells.len());
+                            if let Some(idx) = self.empty_cells.nth(nth) {
+                                self.write_cell(idx, Cell { state: CellState::Food, owner: None, food_age: 0 });
+                            }
                         }
                     }
                 }
             }
             
             let mut new_food_positions = Vec::new();
+            let mut claimed = std::collections::HashSet::new(); // Cells already promised food this step
     
-            for org in &self.organisms {
+            for org_idx in 0..self.organisms.len() {
+                let org = &self.organisms[org_idx];
                 if !org.is_alive {
                     continue;
                 }
@@ -713,42 +1830,177 @@ impl Grid {
                     continue;  // Skip all producer cells in this organism
                 }
                 
-                for cell in &org.cells {
-                    if cell.state != CellStates::Producer {
+                let producers: Vec<(u32, u32)> = org.cells.iter()
+                    .filter(|cell| cell.state == CellState::Producer)
+                    .filter_map(|cell| self.cell_position(org, cell))
+                    .collect();
+                
+                let mut produced = Vec::new();
+                for (cx, cy) in producers {
+                    self.produce_food_around(cx, cy, &mut produced);
+                }
+                
+                // Every piece of food is paid for with the producer's energy,
+                // but a cell another producer already claimed costs nothing
+                let org = &mut self.organisms[org_idx];
+                for pos in produced {
+                    if claimed.contains(&pos) {
                         continue;
                     }
-                    
-                    let (cx, cy) = org.get_cell_position(cell);
-                    let adjacents = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-                    
-                    for (dx, dy) in adjacents.iter() {
-                        let nx = (cx as i32 + dx).max(0).min(self.width as i32 - 1) as u32;
-                        let ny = (cy as i32 + dy).max(0).min(self.height as i32 - 1) as u32;
-                        
-                        if let Some(cell) = self.get_cell(nx, ny) {
-                            // Use food_production_prob value (scale from 0-100 to 0-1)
-                            if cell.state == CellStates::Empty && random::<f32>() < (self.food_production_prob / 100.0) {
-                                new_food_positions.push((nx, ny));
-                            }
-                        }
+                    if org.energy < self.producer_energy_cost {
+                        break;
                     }
+                    org.energy -= self.producer_energy_cost;
+                    claimed.insert(pos);
+                    new_food_positions.push(pos);
                 }
             }
     
-    // Add new food
-    for (x, y) in new_food_positions {
-        self.set_cell(x, y, CellStates::Food, None);
-    }
+            // Add new food, re-checking each cell so food never lands on an organism
+            for (x, y) in new_food_positions {
+                if self.get_cell(x, y).is_some_and(|cell| cell.state == CellState::Empty && cell.owner.is_none()) {
+                    self.set_cell(x, y, CellState::Food, None);
+                }
+            }
             
-            // Update the pixels based on cell states
-            for y in 0..self.height {
-                for x in 0..self.width {
-                    let idx = (y * self.width + x) as usize;
-                    self.pixels[idx] = self.cells[idx].state.to_color();
+            self.finish_step_stats();
+            self.on_event.emit(GridEvent::StepCompleted { tick: self.tick_count });
+        }
+        
+        /// Age every food cell by one step and turn food older than `lifespan` back into empty cells
+        fn spoil_food(&mut self, lifespan: u32) {
+            // Either way food is visited in index order, so both give the same result
+            let food: Vec<usize> = if self.sparse_food_scan {
+                (0..self.food_cells.len()).filter_map(|n| self.food_cells.nth(n)).collect()
+            } else {
+                (0..self.cells.len()).filter(|&idx| self.cells[idx].state == CellState::Food).collect()
+            };
+            for idx in food {
+                self.cells[idx].food_age += 1;
+                if self.cells[idx].food_age > lifespan {
+                    self.write_cell(idx, Cell { state: CellState::Empty, owner: None, food_age: 0 });
+                }
+            }
+        }
+        
+        /// Paint every organism cell in its species color, or tinted with it under `color_by_owner`
+        fn paint_species_colors(&mut self) {
+            for org in &self.organisms {
+                let color = species_color(org.species);
+                for cell in &org.cells {
+                    let idx = self.cell_position(org, cell).and_then(|(x, y)| self.cell_index(x, y));
+                    if let Some(idx) = idx {
+                        if self.cells[idx].owner == Some(org.id) {
+                            self.pixels[idx] = if self.color_by_species {
+                                color
+                            } else {
+                                blend_colors(self.palette.color(cell.state), color)
+                            };
+                        }
+                    }
+                }
+            }
+        }
+        
+        /// Paint the overlay for `render_mode` over the current pixels
+        fn paint_render_mode(&mut self) {
+            let multiplier = self.lifespan_multiplier;
+            match self.render_mode {
+                RenderMode::Normal => {}
+                RenderMode::FoodHeatmap => self.paint_food_heatmap(),
+                RenderMode::OwnerMap => self.paint_organism_cells(|org| owner_color(org.id)),
+                RenderMode::AgeMap => self.paint_organism_cells(|org| age_color(org.lifetime_fraction(multiplier))),
+            }
+        }
+        
+        /// Paint every cell each organism owns in the color `color_of` picks for it
+        fn paint_organism_cells(&mut self, color_of: impl Fn(&Organism) -> u32) {
+            for org in &self.organisms {
+                let color = color_of(org);
+                for cell in &org.cells {
+                    let idx = self.cell_position(org, cell).and_then(|(x, y)| self.cell_index(x, y));
+                    if let Some(idx) = idx {
+                        if self.cells[idx].owner == Some(org.id) {
+                            self.pixels[idx] = color;
+                        }
+                    }
+                }
+            }
+        }
+        
+        /// Shade every empty and food cell by the share of food cells within two cells of it.
+        /// Counts come from a summed-area table, so the whole grid costs one pass.
+        fn paint_food_heatmap(&mut self) {
+            const RADIUS: usize = 2;
+            let (w, h) = (self.width as usize, self.height as usize);
+            // sums[(y * (w + 1)) + x] counts food cells above and left of (x, y)
+            let mut sums = vec![0u32; (w + 1) * (h + 1)];
+            for y in 0..h {
+                let mut row = 0;
+                for x in 0..w {
+                    row += (self.cells[y * w + x].state == CellState::Food) as u32;
+                    sums[(y + 1) * (w + 1) + x + 1] = sums[y * (w + 1) + x + 1] + row;
+                }
+            }
+            for y in 0..h {
+                let (top, bottom) = (y.saturating_sub(RADIUS), (y + RADIUS + 1).min(h));
+                for x in 0..w {
+                    let idx = y * w + x;
+                    if !matches!(self.cells[idx].state, CellState::Empty | CellState::Food) {
+                        continue;
+                    }
+                    let (left, right) = (x.saturating_sub(RADIUS), (x + RADIUS + 1).min(w));
+                    let food = sums[bottom * (w + 1) + right] + sums[top * (w + 1) + left]
+                        - sums[top * (w + 1) + right] - sums[bottom * (w + 1) + left];
+                    let area = ((bottom - top) * (right - left)) as f32;
+                    self.pixels[idx] = heat_color(food as f32 / area);
                 }
             }
         }
         
+        /// Set the cell layout `create_basic_organism` and `origin_of_life` give new organisms,
+        /// as (state, x, y) offsets from the center. An empty list restores the built-in mouth
+        /// with two diagonal producers. Fails, changing nothing, unless exactly one cell sits
+        /// at (0, 0), no two cells overlap and every cell is an organism cell.
+        ///
+        /// ```
+        /// use simulation::{CellState, Grid, OrganismParseError};
+        ///
+        /// let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        /// // A mover-hunter: mouth, killer in front, mover behind
+        /// grid.set_default_anatomy(vec![
+        ///     (CellState::Mouth, 0, 0),
+        ///     (CellState::Killer, 0, -1),
+        ///     (CellState::Mover, 0, 1),
+        /// ]).unwrap();
+        /// grid.origin_of_life();
+        /// assert_eq!(grid.get_cell(10, 9).unwrap().state, CellState::Killer);
+        /// assert_eq!(grid.get_cell(10, 11).unwrap().state, CellState::Mover);
+        ///
+        /// let no_center = grid.set_default_anatomy(vec![(CellState::Mouth, 1, 0)]);
+        /// assert!(matches!(no_center, Err(OrganismParseError::MissingCenter)));
+        /// let food = grid.set_default_anatomy(vec![(CellState::Mouth, 0, 0), (CellState::Food, 1, 0)]);
+        /// assert!(matches!(food, Err(OrganismParseError::EnvironmentCell { x: 1, y: 0 })));
+        /// assert_eq!(grid.default_anatomy().map(|cells| cells.len()), Some(3));
+        /// ```
+        pub fn set_default_anatomy(&mut self, cells: Vec<(CellState, i32, i32)>) -> Result<(), OrganismParseError> {
+            if cells.is_empty() {
+                self.default_anatomy = None;
+                return Ok(());
+            }
+            let layout: Vec<OrganismCell> = cells.iter()
+                .map(|&(state, x, y)| OrganismCell::new(state, x, y))
+                .collect();
+            organism::validate_layout(&layout)?;
+            self.default_anatomy = Some(cells);
+            Ok(())
+        }
+        
+        /// The layout set with `set_default_anatomy`, or `None` for the built-in one
+        pub fn default_anatomy(&self) -> Option<&[(CellState, i32, i32)]> {
+            self.default_anatomy.as_deref()
+        }
+        
         /// Create an initial organism (the "origin of life")
         pub fn origin_of_life(&mut self) {
             let x = self.width / 2;
@@ -756,28 +2008,868 @@ impl Grid {
             self.create_basic_organism(x, y);
         }
         
-        /// Reset the grid to initial state
+        /// Reset the grid to initial state. Walls and hazards stay unless `clear_walls` is set.
         pub fn reset(&mut self, clear_walls: bool) {
-            // Clear all cells except walls if specified
+            // Clear all cells except walls and hazards if specified
             for y in 0..self.height {
                 for x in 0..self.width {
                     let idx = (y * self.width + x) as usize;
-                    if clear_walls || self.cells[idx].state != CellStates::Wall {
-                        self.cells[idx] = Cell { state: CellStates::Empty, owner: None };
+                    let terrain = matches!(self.cells[idx].state, CellState::Wall | CellState::Hazard);
+                    if clear_walls || !terrain {
+                        self.write_cell(idx, Cell { state: CellState::Empty, owner: None, food_age: 0 });
                     }
                 }
             }
             
-            // Clear all organisms
+            // Clear all organisms. The ID counter keeps going so old IDs never come back.
             self.organisms.clear();
             
-            // Reset organism ID counter
-            self.next_organism_id = 0;
-            
-            // Update pixels
-            for (idx, cell) in self.cells.iter().enumerate() {
-                self.pixels[idx] = cell.state.to_color();
+            self.tick_count = 0;
+            self.death_stats = DeathStats::default();
+        }
+        
+        /// Turn every food cell back into an empty cell, leaving organisms and walls alone
+        pub fn clear_food(&mut self) {
+            for idx in 0..self.cells.len() {
+                if self.cells[idx].state == CellState::Food {
+                    self.write_cell(idx, Cell { state: CellState::Empty, owner: None, food_age: 0 });
+                }
+            }
+        }
+        
+        /// Remove every organism and empty the cells they held, keeping walls and food.
+        /// Unlike deaths, removed organisms leave no food behind. IDs keep counting up.
+        pub fn clear_organisms(&mut self) {
+            for idx in 0..self.cells.len() {
+                if self.cells[idx].owner.is_some() {
+                    self.write_cell(idx, Cell { state: CellState::Empty, owner: None, food_age: 0 });
+                }
+            }
+            self.organisms.clear();
+        }
+    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eyes_steer_movers_toward_food_more_often_than_chance() {
+        let trials = 40;
+        let moved_toward_food = (0..trials).filter(|&seed| {
+            let mut grid = Grid::new_seeded(40, 40, seed).unwrap();
+            grid.food_production_prob = 0.0;
+            let mut organism = Organism::new(0, 10, 20);
+            organism.add_cell(CellState::Mover, 0, 1);
+            organism.add_cell(CellState::Eye, 0, -1);
+            organism.cells[2].direction = Some(Direction::Right);
+            organism.energy = organism.max_energy();
+            assert!(grid.add_organism(organism));
+            grid.set_cell(14, 19, CellState::Food, None);
+
+            grid.step();
+            grid.organisms[0].x == 11
+        }).count();
+        // A random pick of four directions would head right about a quarter of the time
+        assert!(moved_toward_food > trials as usize * 3 / 4, "{} of {}", moved_toward_food, trials);
+    }
+
+    /// A seeded 60x60 grid with plenty of food, grown from one organism for `steps` steps
+    fn grown_grid(seed: u64, steps: u32) -> Grid {
+        let mut grid = Grid::new_seeded(60, 60, seed).unwrap();
+        grid.food_production_prob = 10.0;
+        grid.origin_of_life();
+        grid.step_n(steps);
+        grid
+    }
+
+    #[test]
+    fn same_seed_gives_identical_grids() {
+        let (a, b) = (grown_grid(9, 500), grown_grid(9, 500));
+        assert!(a.organisms.len() > 1);
+        assert_eq!(a.pixels, b.pixels);
+        let bodies = |grid: &Grid| grid.organisms.iter()
+            .map(|org| (org.id, org.x, org.y, org.rotation, org.food_collected, org.lifetime))
+            .collect::<Vec<_>>();
+        assert_eq!(bodies(&a), bodies(&b));
+    }
+
+    #[test]
+    fn parallel_and_sequential_updates_agree() {
+        // Pinned value, so the same test run with and without the `rayon` feature
+        // checks that both update paths produce this exact grid
+        let grid = grown_grid(9, 200);
+        let hash = grid.pixels.iter().fold(0u64, |h, &p| h.wrapping_mul(31).wrapping_add(p as u64));
+        assert_eq!(hash, 14098859188026092920);
+    }
+
+    #[test]
+    fn one_mouth_eats_one_adjacent_food_once() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        // A second cell so one food isn't already enough to reproduce
+        let mut organism = Organism::new(0, 10, 10);
+        organism.add_cell(CellState::Armor, -1, 0);
+        assert!(grid.add_organism(organism));
+        grid.set_cell(11, 10, CellState::Food, None);
+
+        grid.step();
+        assert_eq!(grid.organisms[0].food_collected, 1);
+        assert_eq!(grid.get_cell(11, 10).unwrap().state, CellState::Empty);
+    }
+
+    #[test]
+    fn wrapping_mover_leaves_the_right_edge_and_comes_back_on_the_left() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.wrap_edges = true;
+        let mut organism = Organism::new(0, 19, 10);
+        organism.add_cell(CellState::Mover, 0, 1);
+        organism.move_direction = Direction::Right;
+        assert!(grid.add_organism(organism));
+
+        grid.step();
+        let id = grid.organisms[0].id;
+        assert_eq!((grid.organisms[0].x, grid.organisms[0].y), (0, 10));
+        assert_eq!(grid.get_cell(0, 11).unwrap().owner, Some(id));
+        assert_eq!(grid.get_cell(19, 10).unwrap().owner, None);
+    }
+
+    #[test]
+    fn killer_against_the_edge_leaves_itself_alone() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        // Killers in the top-left corner and flush against the left edge
+        let mut organism = Organism::new(0, 1, 0);
+        organism.add_cell(CellState::Killer, -1, 0);
+        organism.add_cell(CellState::Killer, -1, 1);
+        assert!(grid.add_organism(organism));
+
+        grid.step_n(5);
+        assert!(grid.organisms[0].is_alive);
+        assert_eq!(grid.organisms[0].health, 3);
+    }
+
+    #[test]
+    fn organism_past_its_lifespan_dies_of_old_age() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.create_basic_organism(10, 10);
+        let id = grid.organisms[0].id;
+        grid.organisms[0].lifetime = grid.organisms[0].max_lifespan(grid.lifespan_multiplier);
+
+        grid.step();
+        assert!(grid.organisms.is_empty());
+        assert_eq!(grid.recent_deaths(), &[(id, DeathCause::OldAge)]);
+    }
+
+    #[test]
+    fn organism_worn_down_by_a_killer_is_killed() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        let mut victim = Organism::new(0, 10, 10);
+        victim.add_cell(CellState::Producer, -1, 0);
+        assert!(grid.add_organism(victim));
+        let mut hunter = Organism::new(0, 12, 10);
+        hunter.add_cell(CellState::Killer, -1, 0); // Next to the victim's mouth
+        assert!(grid.add_organism(hunter));
+        let (victim_id, hunter_id) = (grid.organisms[0].id, grid.organisms[1].id);
+
+        // One point of damage a step against two points of health
+        grid.step();
+        assert!(grid.recent_deaths().is_empty());
+        grid.step();
+        assert_eq!(grid.recent_deaths(), &[(victim_id, DeathCause::Killed)]);
+        assert_eq!(grid.organisms.iter().map(|org| org.id).collect::<Vec<_>>(), vec![hunter_id]);
+    }
+
+    #[test]
+    fn dropped_food_lands_evenly_on_empty_cells_at_the_scan_odds() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_drop_prob = 1.0; // One drop attempt a step on a grid this small
+        grid.draw_wall_rect(0, 0, 10, 20, true); // Left half can't take food
+        let steps = 4000;
+        let mut per_column = [0u32; 20];
+        for _ in 0..steps {
+            grid.step();
+            for (idx, cell) in grid.cells.iter().enumerate() {
+                if cell.state == CellState::Food {
+                    per_column[idx % 20] += 1;
+                }
+            }
+            grid.clear_food();
+        }
+
+        // Like picking any cell and keeping only empty ones: half the drops land,
+        // spread evenly over the empty columns
+        assert!(per_column[..10].iter().all(|&count| count == 0));
+        let landed: u32 = per_column.iter().sum();
+        assert!((1700..2300).contains(&landed), "{} of {} landed", landed, steps);
+        assert!(per_column[10..].iter().all(|&count| (120..280).contains(&count)), "{:?}", per_column);
+    }
+
+    /// Health of an armored five-cell organism after one step facing a killer across its armor
+    fn health_after_hit_on_armor(killer_damage: u32, armor_reduction: u32) -> u32 {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.killer_damage = killer_damage;
+        grid.armor_reduction = armor_reduction;
+        let mut victim = Organism::new(0, 10, 10);
+        victim.add_cell(CellState::Armor, 1, 0);
+        for (x, y) in [(-1, 0), (0, 1), (0, -1)] {
+            victim.add_cell(CellState::Producer, x, y);
+        }
+        assert!(grid.add_organism(victim));
+        let mut hunter = Organism::new(0, 13, 10);
+        hunter.add_cell(CellState::Killer, -1, 0); // Touches only the armor
+        assert!(grid.add_organism(hunter));
+
+        grid.step();
+        grid.organisms[0].health
+    }
+
+    #[test]
+    fn armor_takes_its_reduction_off_each_hit() {
+        assert_eq!(health_after_hit_on_armor(3, 1), 5 - 2);
+        assert_eq!(health_after_hit_on_armor(3, 0), 5 - 3);
+        assert_eq!(health_after_hit_on_armor(3, 3), 5);
+    }
+
+    #[test]
+    fn mutual_killers_die_the_same_way_every_run() {
+        let run = || {
+            let mut grid = Grid::new_seeded(20, 20, 3).unwrap();
+            grid.food_production_prob = 0.0;
+            for (x, dx) in [(10, 1), (13, -1)] {
+                let mut organism = Organism::new(0, x, 10);
+                organism.add_cell(CellState::Killer, dx, 0); // The two killers touch
+                organism.add_cell(CellState::Producer, 0, 1);
+                assert!(grid.add_organism(organism));
             }
+            let mut deaths = Vec::new();
+            for _ in 0..10 {
+                grid.step();
+                deaths.extend(grid.recent_deaths().iter().map(|&(id, cause)| (grid.tick_count(), id, cause)));
+            }
+            deaths
+        };
+        let first = run();
+        assert!(!first.is_empty());
+        assert!(first.iter().all(|&(_, _, cause)| cause == DeathCause::Killed));
+        for _ in 0..5 {
+            assert_eq!(run(), first);
         }
-        // ... other methods ...
-    }
\ No newline at end of file
+    }
+
+    #[test]
+    fn tick_count_counts_steps_until_reset() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        for _ in 0..10 {
+            grid.step();
+        }
+        assert_eq!(grid.tick_count(), 10);
+        grid.reset(true);
+        assert_eq!(grid.tick_count(), 0);
+    }
+
+    #[test]
+    fn organism_with_a_cell_above_and_left_of_the_origin_is_rejected() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        let mut organism = Organism::new(0, 0, 0);
+        organism.add_cell(CellState::Producer, -1, -1);
+        assert!(!grid.add_organism(organism));
+        assert!(grid.organisms.is_empty());
+        // Nothing was written anywhere, least of all at the far corner
+        assert!(grid.cells.iter().all(|cell| cell.state == CellState::Empty && cell.owner.is_none()));
+    }
+
+    #[test]
+    fn restored_snapshot_replays_the_same_steps() {
+        let mut grid = grown_grid(4, 20);
+        let snapshot = grid.snapshot();
+        grid.step_n(50);
+        let (pixels, tick) = (grid.pixels.clone(), grid.tick_count());
+
+        grid.restore(&snapshot);
+        assert_eq!(grid.pixels, snapshot.pixels);
+        grid.step_n(50);
+        assert_eq!(grid.tick_count(), tick);
+        assert_eq!(grid.pixels, pixels);
+    }
+
+    #[test]
+    fn growing_keeps_the_old_cells_and_adds_empty_ones() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        grid.create_basic_organism(5, 5);
+        grid.set_cell(9, 9, CellState::Wall, None);
+        grid.resize(15, 12).unwrap();
+
+        assert_eq!((grid.width, grid.height), (15, 12));
+        assert_eq!(grid.get_cell(9, 9).unwrap().state, CellState::Wall);
+        let id = grid.organisms[0].id;
+        assert_eq!(grid.get_cell(5, 5).unwrap().owner, Some(id));
+        for (x, y) in (0..15).flat_map(|x| (0..12).map(move |y| (x, y))).filter(|&(x, y)| x >= 10 || y >= 10) {
+            assert_eq!(grid.get_cell(x, y).unwrap().state, CellState::Empty);
+        }
+    }
+
+    #[test]
+    fn shrinking_removes_organisms_that_no_longer_fit() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.create_basic_organism(3, 3);
+        grid.create_basic_organism(9, 9); // Its producer at (10, 10) falls outside
+        let kept = grid.organisms[0].id;
+        grid.resize(10, 10).unwrap();
+
+        assert_eq!(grid.organisms.iter().map(|org| org.id).collect::<Vec<_>>(), vec![kept]);
+        assert!(grid.cells.iter().all(|cell| cell.owner.is_none() || cell.owner == Some(kept)));
+        assert_eq!(grid.get_cell(9, 9).unwrap().state, CellState::Empty);
+        assert_eq!(grid.pixels.len(), 100);
+    }
+
+    #[test]
+    fn clear_food_keeps_walls_and_organisms() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        grid.create_basic_organism(5, 5);
+        grid.set_cell(1, 1, CellState::Wall, None);
+        grid.set_cell(2, 2, CellState::Food, None);
+        grid.clear_food();
+
+        assert_eq!(grid.get_cell(1, 1).unwrap().state, CellState::Wall);
+        assert_eq!(grid.get_cell(2, 2).unwrap().state, CellState::Empty);
+        assert_eq!(grid.organisms.len(), 1);
+    }
+
+    #[test]
+    fn clear_organisms_keeps_walls_food_and_ids() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        grid.create_basic_organism(5, 5);
+        grid.set_cell(1, 1, CellState::Wall, None);
+        grid.set_cell(2, 2, CellState::Food, None);
+        let next_id = grid.next_organism_id;
+        grid.clear_organisms();
+
+        assert!(grid.organisms.is_empty());
+        assert!(grid.cells.iter().all(|cell| cell.owner.is_none()));
+        assert_eq!(grid.get_cell(5, 5).unwrap().state, CellState::Empty);
+        assert_eq!(grid.get_cell(1, 1).unwrap().state, CellState::Wall);
+        assert_eq!(grid.get_cell(2, 2).unwrap().state, CellState::Food);
+        assert_eq!(grid.next_organism_id, next_id);
+    }
+
+    #[test]
+    fn walled_off_producer_runs_out_of_energy_and_stops_producing() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 100.0;
+        grid.energy_regen = 0;
+        grid.food_lifespan = Some(1);
+        grid.create_basic_organism(10, 10);
+        // Walls all around, leaving only the 3x3 block the organism sits in
+        grid.draw_wall_rect(0, 0, 20, 20, true);
+        for (x, y) in walls(&grid) {
+            if x.abs_diff(10) <= 1 && y.abs_diff(10) <= 1 {
+                grid.clear_wall(x, y);
+            }
+        }
+
+        grid.step();
+        assert!(grid.food_cell_count() > 0);
+        grid.step_n(10);
+        assert_eq!(grid.organisms[0].energy, 0);
+        for _ in 0..20 {
+            grid.step();
+            assert!(grid.organisms[0].is_alive);
+            assert_eq!(grid.food_cell_count(), 0);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip_carries_on_like_the_original() {
+        let mut grid = grown_grid(5, 100);
+        let mut loaded = Grid::from_json(&grid.to_json()).unwrap();
+        assert_eq!(loaded.next_organism_id, grid.next_organism_id);
+        assert_eq!(loaded.food_production_prob, grid.food_production_prob);
+        assert_eq!(loaded.pixels, grid.pixels);
+
+        // The RNG is part of the snapshot, so both grids go on to the same future
+        grid.step_n(100);
+        loaded.step_n(100);
+        assert_eq!(loaded.pixels, grid.pixels);
+        let bodies = |grid: &Grid| grid.organisms.iter()
+            .map(|org| (org.id, org.x, org.y, org.rotation, org.food_collected, org.energy, org.lifetime))
+            .collect::<Vec<_>>();
+        assert!(!bodies(&grid).is_empty());
+        assert_eq!(bodies(&loaded), bodies(&grid));
+    }
+
+    #[test]
+    fn armor_beside_the_hit_edge_blocks_only_when_enabled() {
+        let health_after_hit = |armor_blocks_adjacent: bool| {
+            let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+            grid.food_production_prob = 0.0;
+            grid.armor_blocks_adjacent = armor_blocks_adjacent;
+            let mut victim = Organism::new(0, 10, 10);
+            victim.add_cell(CellState::Producer, 1, 0);
+            victim.add_cell(CellState::Armor, 1, -1); // Beside the producer's right edge
+            assert!(grid.add_organism(victim));
+            let mut hunter = Organism::new(0, 13, 10);
+            hunter.add_cell(CellState::Killer, -1, 0); // Touches only the producer, from the right
+            assert!(grid.add_organism(hunter));
+
+            grid.step();
+            grid.organisms[0].health
+        };
+        assert_eq!(health_after_hit(false), 3 - 1);
+        assert_eq!(health_after_hit(true), 3);
+    }
+
+    #[test]
+    fn producers_in_the_light_make_more_food_than_in_the_dark() {
+        let mut grid = Grid::new_seeded(40, 40, 1).unwrap();
+        grid.food_production_prob = 50.0;
+        grid.producer_energy_cost = 0;
+        grid.set_light_gradient(1.0, 0.1);
+        grid.create_basic_organism(20, 2);
+        grid.create_basic_organism(20, 37);
+
+        let (mut top, mut bottom) = (0, 0);
+        for _ in 0..100 {
+            grid.step();
+            // Count and clear the new food, so nothing gets eaten or crowds out the next step
+            for y in 0..40 {
+                for x in 0..40 {
+                    if grid.get_cell(x, y).unwrap().state == CellState::Food {
+                        if y < 20 { top += 1 } else { bottom += 1 }
+                        grid.set_cell(x, y, CellState::Empty, None);
+                    }
+                }
+            }
+        }
+        assert!(bottom > 0);
+        assert!(top > 3 * bottom, "top {} vs bottom {}", top, bottom);
+    }
+
+    #[test]
+    fn changes_after_a_move_are_the_cells_entered_and_left() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.food_drop_prob = 0.0;
+        let mut mover = Organism::new(0, 10, 10);
+        mover.add_cell(CellState::Mover, 1, 0);
+        mover.add_cell(CellState::Producer, 0, 1);
+        assert!(grid.add_organism(mover));
+        let body = |grid: &Grid| (0..20).flat_map(|y| (0..20).map(move |x| (x, y)))
+            .filter_map(|(x, y)| {
+                let cell = grid.get_cell(x, y).unwrap();
+                cell.owner.map(|_| ((x, y), cell.state))
+            })
+            .collect::<HashMap<_, _>>();
+
+        grid.take_changes();
+        let before = body(&grid);
+        let start = (grid.organisms[0].x, grid.organisms[0].y);
+        while (grid.organisms[0].x, grid.organisms[0].y) == start {
+            grid.step();
+        }
+        let after = body(&grid);
+
+        let mut expected: Vec<(u32, u32, CellState)> = before.keys().chain(after.keys())
+            .filter(|pos| before.get(pos) != after.get(pos))
+            .map(|&(x, y)| (x, y, after.get(&(x, y)).copied().unwrap_or(CellState::Empty)))
+            .collect();
+        expected.sort_by_key(|&(x, y, _)| (y, x));
+        expected.dedup();
+        let mut changes = grid.take_changes();
+        changes.sort_by_key(|&(x, y, _)| (y, x));
+        assert!(!changes.is_empty());
+        assert_eq!(changes, expected);
+        assert!(grid.take_changes().is_empty());
+    }
+
+    /// Times the first organism moved over `steps` steps
+    fn moves_over(grid: &mut Grid, steps: u32) -> u32 {
+        let mut moves = 0;
+        for _ in 0..steps {
+            grid.step();
+            if matches!(grid.recent_actions().first(), Some((_, OrganismAction::Moved { .. }))) {
+                moves += 1;
+            }
+        }
+        moves
+    }
+
+    /// A mouth-and-mover organism in the middle of an otherwise empty 40x40 grid
+    fn lone_mover_grid() -> Grid {
+        let mut grid = Grid::new_seeded(40, 40, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.food_drop_prob = 0.0;
+        grid.reproduction_cooldown = u32::MAX; // All the food it eats mustn't turn into offspring
+        let mut mover = Organism::new(0, 20, 20);
+        mover.add_cell(CellState::Mover, 1, 0);
+        assert!(grid.add_organism(mover));
+        grid
+    }
+
+    #[test]
+    fn smelling_food_makes_an_eyeless_mover_linger() {
+        let mover_moves = |sense_radius: u32, food: bool| {
+            let mut grid = lone_mover_grid();
+            grid.sense_radius = sense_radius;
+            for y in 0..40 {
+                for x in 0..40 {
+                    if food && grid.get_cell(x, y).unwrap().owner.is_none() {
+                        grid.set_cell(x, y, CellState::Food, None);
+                    }
+                }
+            }
+            moves_over(&mut grid, 200)
+        };
+
+        let (barren, feast) = (mover_moves(2, false), mover_moves(2, true));
+        assert!(feast * 3 < barren * 2, "{} moves among food, {} without", feast, barren);
+        // Without a sense of smell the food makes no difference
+        let unsmelled = mover_moves(0, true);
+        assert!(unsmelled * 3 > barren * 2, "{} moves among unsmelled food, {} without", unsmelled, barren);
+    }
+
+    #[test]
+    fn starved_mover_dies_before_a_fed_one_under_metabolism() {
+        let lifetime = |food: bool| {
+            let mut grid = lone_mover_grid();
+            grid.metabolism_enabled = true;
+            for y in 0..40 {
+                for x in 0..40 {
+                    if food && grid.get_cell(x, y).unwrap().owner.is_none() {
+                        grid.set_cell(x, y, CellState::Food, None);
+                    }
+                }
+            }
+            let mut steps = 0;
+            while grid.organisms.iter().any(|org| org.is_alive) {
+                grid.step();
+                steps += 1;
+            }
+            (steps, grid.death_histogram())
+        };
+
+        let (starved_steps, starved_deaths) = lifetime(false);
+        let (fed_steps, fed_deaths) = lifetime(true);
+        assert_eq!(starved_deaths.starved, 1);
+        assert_eq!(fed_deaths.total(), 1);
+        assert!(starved_steps * 5 < fed_steps, "starved after {} steps, fed lived {}", starved_steps, fed_steps);
+    }
+
+    #[test]
+    fn eyeless_movers_drift_toward_food_to_the_east() {
+        // Share of runs, one per seed, in which the mover ends up east of where it started
+        let east_share = |food_gradient_movement: bool| {
+            let drifted_east = (0..40).filter(|&seed| {
+                let mut grid = lone_mover_grid();
+                grid.rng = ChaCha8Rng::seed_from_u64(seed);
+                grid.food_gradient_movement = food_gradient_movement;
+                // Food from just past the mover cell on, so it shows in the block checked to the east
+                for y in 0..40 {
+                    for x in 22..40 {
+                        grid.set_cell(x, y, CellState::Food, None);
+                    }
+                }
+                grid.step_n(20);
+                grid.organisms[0].x > 20
+            }).count();
+            drifted_east as f32 / 40.0
+        };
+        assert!(east_share(true) > 0.9, "{}", east_share(true));
+        assert!(east_share(false) < 0.7, "{}", east_share(false));
+    }
+
+    #[test]
+    fn organisms_over_the_size_cap_do_not_reproduce() {
+        let births = |max_organism_cells: usize| {
+            let mut grid = Grid::new_seeded(30, 30, 1).unwrap();
+            grid.max_organism_cells = max_organism_cells;
+            grid.create_basic_organism(15, 15); // Three cells
+            grid.organisms[0].food_collected = 100;
+            grid.process_reproduction();
+            grid.organisms.len() - 1
+        };
+        assert_eq!(births(2), 0);
+        assert_eq!(births(3), 1);
+        assert_eq!(births(0), 1);
+    }
+
+    #[test]
+    fn diagonal_killer_hits_only_with_diagonal_neighbors() {
+        let health_after_step = |use_diagonal_neighbors: bool| {
+            let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+            grid.food_production_prob = 0.0;
+            grid.use_diagonal_neighbors = use_diagonal_neighbors;
+            let mut victim = Organism::new(0, 10, 10);
+            victim.add_cell(CellState::Producer, -1, 0);
+            assert!(grid.add_organism(victim));
+            let mut hunter = Organism::new(0, 12, 11);
+            hunter.add_cell(CellState::Killer, -1, 0); // At (11, 11), diagonal to the victim's mouth
+            assert!(grid.add_organism(hunter));
+
+            grid.step();
+            grid.organisms[0].health
+        };
+        assert_eq!(health_after_step(false), 2);
+        assert_eq!(health_after_step(true), 2 - 1);
+    }
+
+    #[test]
+    fn killer_is_credited_when_its_prey_dies() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        let mut prey = Organism::new(0, 10, 10);
+        prey.add_cell(CellState::Producer, -1, 0); // Two health, so it takes two hits
+        assert!(grid.add_organism(prey));
+        let prey_id = grid.organisms[0].id;
+        let mut hunter = Organism::new(0, 12, 10);
+        hunter.add_cell(CellState::Killer, -1, 0);
+        assert!(grid.add_organism(hunter));
+        let hunter_id = grid.organisms[1].id;
+        let hunter_kills = |grid: &Grid| grid.organisms.iter().find(|org| org.id == hunter_id).unwrap().kills;
+
+        grid.step();
+        assert!(grid.organisms.iter().any(|org| org.id == prey_id && org.is_alive));
+        assert_eq!(hunter_kills(&grid), 0); // Wounding isn't killing
+        grid.step();
+        assert!(!grid.organisms.iter().any(|org| org.id == prey_id && org.is_alive));
+        assert_eq!(hunter_kills(&grid), 1);
+        assert_eq!(grid.death_histogram().killed, 1);
+    }
+
+    #[test]
+    fn mover_that_cannot_pay_to_move_starves() {
+        use std::sync::{Arc, Mutex};
+        let mut grid = lone_mover_grid();
+        grid.move_food_cost = 0.5;
+        let id = grid.organisms[0].id;
+        assert_eq!(grid.organisms[0].food_collected, 0);
+        let deaths = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&deaths);
+        grid.set_on_event(move |event| if let GridEvent::OrganismDied { id, cause } = event {
+            seen.lock().unwrap().push((id, cause));
+        });
+
+        // The first move only runs up half a food of debt; hungry from then on, it starves
+        // `starvation_steps` steps later
+        grid.step_n(grid.starvation_steps - 1);
+        assert!(deaths.lock().unwrap().is_empty());
+        grid.step_n(2);
+        assert_eq!(*deaths.lock().unwrap(), vec![(id, DeathCause::Starved)]);
+        assert!(grid.organisms.is_empty());
+        assert_eq!(grid.death_histogram().starved, 1);
+    }
+
+    #[test]
+    fn heal_cell_restores_health_every_heal_interval_steps() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.heal_interval = 5;
+        let mut patient = Organism::new(0, 10, 10);
+        patient.add_cell(CellState::Heal, 1, 0);
+        patient.add_cell(CellState::Armor, -1, 0);
+        patient.add_cell(CellState::Armor, 0, 1);
+        patient.add_cell(CellState::Armor, 0, -1);
+        assert!(grid.add_organism(patient));
+        grid.organisms[0].health = 1; // Badly hurt
+
+        let mut healed_at = Vec::new();
+        for step in 1..=30 {
+            let before = grid.organisms[0].health;
+            grid.step();
+            if grid.organisms[0].health > before {
+                assert_eq!(grid.organisms[0].health, before + 1);
+                healed_at.push(step);
+            }
+        }
+        // One health every five steps until it is back to one per cell
+        assert_eq!(healed_at.len(), 4);
+        assert!(healed_at.windows(2).all(|pair| pair[1] - pair[0] == 5), "{:?}", healed_at);
+        assert_eq!(grid.organisms[0].health, 5);
+    }
+
+    #[test]
+    fn hazards_damage_the_organism_cells_touching_them() {
+        let health_after_step = |touching: CellState, insta_kill: bool| {
+            let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+            grid.food_production_prob = 0.0;
+            grid.insta_kill = insta_kill;
+            grid.killer_damage = 2;
+            let mut organism = Organism::new(0, 10, 10);
+            organism.add_cell(touching, 1, 0);
+            organism.add_cell(CellState::Producer, -1, 0);
+            assert!(grid.add_organism(organism));
+            grid.set_cell(12, 10, CellState::Hazard, None);
+            grid.step();
+            grid.organisms.first().map_or(0, |org| org.health)
+        };
+        assert_eq!(health_after_step(CellState::Producer, false), 3 - 2);
+        assert_eq!(health_after_step(CellState::Armor, false), 3 - 1); // Armor takes its reduction off
+        assert_eq!(health_after_step(CellState::Producer, true), 0);
+    }
+
+    #[test]
+    fn movers_never_step_onto_hazards() {
+        let mut grid = lone_mover_grid();
+        grid.killer_damage = 0; // Harmless, so the mover lives long enough to keep trying
+        grid.draw_hazard_rect(17, 17, 7, 7, false);
+        let hazards: Vec<usize> = (0..grid.cells.len())
+            .filter(|&idx| grid.cells[idx].state == CellState::Hazard)
+            .collect();
+        assert_eq!(hazards.len(), 24);
+
+        let moves = moves_over(&mut grid, 150);
+        assert!(moves > 10);
+        assert!(hazards.iter().all(|&idx| grid.cells[idx].state == CellState::Hazard && grid.cells[idx].owner.is_none()));
+        let (x, y) = (grid.organisms[0].x, grid.organisms[0].y);
+        assert!((18..=22).contains(&x) && (18..=22).contains(&y));
+    }
+
+    #[test]
+    fn failed_birth_keeps_the_parents_energy() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.metabolism_enabled = true;
+        grid.create_basic_organism(10, 10);
+        // Wall in every cell the parent isn't using, so the child fits nowhere
+        grid.draw_wall_rect(0, 0, 20, 20, true);
+        grid.organisms[0].food_collected = 100;
+        grid.organisms[0].energy = 20;
+
+        grid.process_reproduction();
+        assert_eq!(grid.organisms.len(), 1);
+        assert_eq!(grid.organisms[0].food_collected, 100); // Nothing is spent on a child that never arrives
+        assert_eq!(grid.organisms[0].energy, 20);
+    }
+
+    #[test]
+    fn boxed_in_parent_stays_eligible() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.reproduction_cooldown = 5;
+        grid.create_basic_organism(10, 10);
+        grid.draw_wall_rect(0, 0, 20, 20, true);
+        grid.organisms[0].food_collected = 100;
+        grid.organisms[0].steps_since_reproduction = 5;
+        let next_id = grid.next_organism_id;
+
+        grid.process_reproduction();
+        assert_eq!(grid.organisms.len(), 1);
+        assert_eq!(grid.organisms[0].steps_since_reproduction, 5);
+        assert_eq!(grid.next_organism_id, next_id);
+
+        // Once there is room, the very next attempt succeeds and takes the next ID
+        for (x, y) in walls(&grid) {
+            grid.clear_wall(x, y);
+        }
+        grid.process_reproduction();
+        assert_eq!(grid.organisms.len(), 2);
+        assert_eq!(grid.organisms[1].id, next_id);
+        assert_eq!(grid.organisms[0].steps_since_reproduction, 0);
+        assert_eq!(grid.next_organism_id, next_id + 1);
+    }
+
+    #[test]
+    fn food_spoils_the_step_after_its_lifespan() {
+        let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.food_lifespan = Some(10);
+        grid.set_cell(5, 5, CellState::Food, None);
+
+        grid.step_n(10);
+        assert_eq!(grid.get_cell(5, 5).unwrap().state, CellState::Food);
+        grid.step();
+        assert_eq!(grid.get_cell(5, 5).unwrap().state, CellState::Empty);
+    }
+
+    #[test]
+    fn births_and_deaths_reach_the_event_hook() {
+        use std::sync::{Arc, Mutex};
+        let mut grid = Grid::new_seeded(40, 40, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.origin_of_life();
+        let parent = grid.organisms[0].id;
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&events);
+        grid.set_on_event(move |event| seen.lock().unwrap().push(event));
+
+        grid.organisms[0].food_collected = 100;
+        grid.step();
+        let child = grid.organisms.iter().find(|org| org.parent_id == Some(parent)).unwrap().id;
+        assert!(events.lock().unwrap().contains(&GridEvent::OrganismBorn { id: child, parent_id: parent }));
+
+        grid.organisms[0].die(DeathCause::Killed);
+        grid.step();
+        assert!(events.lock().unwrap().contains(&GridEvent::OrganismDied { id: parent, cause: DeathCause::Killed }));
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    #[test]
+    fn event_hook_needs_no_locking_without_rayon() {
+        use std::cell::Cell;
+        use std::rc::Rc;
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        let steps = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&steps);
+        grid.set_on_event(move |event| {
+            if let GridEvent::StepCompleted { .. } = event {
+                counter.set(counter.get() + 1);
+            }
+        });
+        grid.step_n(3);
+        assert_eq!(steps.get(), 3);
+    }
+
+    /// Coordinates of every wall cell, row by row
+    fn walls(grid: &Grid) -> Vec<(u32, u32)> {
+        (0..grid.height).flat_map(|y| (0..grid.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| grid.get_cell(x, y).unwrap().state == CellState::Wall)
+            .collect()
+    }
+
+    #[test]
+    fn wall_rect_is_just_the_border() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        grid.place_wall_rect(2, 3, 4, 3);
+        assert_eq!(walls(&grid), vec![
+            (2, 3), (3, 3), (4, 3), (5, 3),
+            (2, 4), (5, 4),
+            (2, 5), (3, 5), (4, 5), (5, 5),
+        ]);
+    }
+
+    #[test]
+    fn wall_rect_is_clipped_at_the_edge() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        // Right and bottom sides fall off the grid and are dropped
+        grid.place_wall_rect(7, 8, 5, 5);
+        assert_eq!(walls(&grid), vec![(7, 8), (8, 8), (9, 8), (7, 9)]);
+    }
+
+    #[test]
+    fn wall_line_is_clipped_and_skips_organisms() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        grid.create_basic_organism(4, 4);
+        grid.place_wall_line(0, 4, 14, 4);
+        let expected: Vec<(u32, u32)> = (0..10).filter(|&x| x != 4).map(|x| (x, 4)).collect();
+        assert_eq!(walls(&grid), expected);
+        assert_eq!(grid.get_cell(4, 4).unwrap().owner, Some(grid.organisms[0].id));
+    }
+
+    #[test]
+    fn sparse_food_scan_gives_the_same_run() {
+        let run = |sparse: bool| {
+            let mut grid = Grid::new_seeded(40, 40, 5).unwrap();
+            grid.food_production_prob = 10.0;
+            grid.food_lifespan = Some(20);
+            grid.sparse_food_scan = sparse;
+            grid.origin_of_life();
+            grid.step_n(200);
+            (grid.pixels.clone(), grid.food_cell_count(), grid.organisms.len())
+        };
+        assert_eq!(run(true), run(false));
+    }
+}
@@ -1,12 +1,17 @@
 // simulation/src/organism.rs
 
 use rand::Rng;
-use rand::seq::SliceRandom; // Add this import
-use rand::random;
-use crate::CellStates;
+use std::fmt;
+use crate::{Brain, CellState, Reaction};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Energy an organism can store per cell of its body
+const ENERGY_PER_CELL: u32 = 10;
 
 /// Direction for movement and facing
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Direction {
     Up = 0,
     Right = 1, 
@@ -16,8 +21,8 @@ pub enum Direction {
 
 impl Direction {
     /// Get a random direction
-    pub fn random() -> Self {
-        let dir = rand::thread_rng().gen_range(0..4);
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let dir = rng.gen_range(0..4);
         match dir {
             0 => Direction::Up,
             1 => Direction::Right,
@@ -48,23 +53,45 @@ impl Direction {
     }
 }
 
+/// Why an organism died
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeathCause {
+    OldAge,
+    Killed,
+    Starved,
+}
+
+/// What an organism did during one step, for debugging and replay
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OrganismAction {
+    Moved { from: (u32, u32), to: (u32, u32) }, // Center position before and after
+    Rotated { to: Direction },
+    Ate { count: u32 },  // Reported by the grid, which does the eating, when nothing else happened
+    Blocked,             // Tried to move and to rotate but had no room for either
+    DiedOfAge,
+    Idle,
+}
+
 /// A cell in an organism, with its state and relative position to the organism center
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OrganismCell {
-    pub state: CellStates,
+    pub state: CellState,
     pub x: i32,   // Relative x position from organism center
     pub y: i32,   // Relative y position from organism center
     pub direction: Option<Direction>, // For cells that have direction (like eyes)
 }
 
 impl OrganismCell {
-    pub fn new(state: CellStates, x: i32, y: i32) -> Self {
+    pub fn new(state: CellState, x: i32, y: i32) -> Self {
         OrganismCell {
             state,
             x,
             y,
-            direction: if state == CellStates::Eye { 
-                Some(Direction::random()) 
+            direction: if state == CellState::Eye { 
+                Some(Direction::Up) 
             } else { 
                 None 
             },
@@ -97,8 +124,34 @@ impl OrganismCell {
     }
 }
 
+/// Percent chances (0-100) used when an offspring mutates
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MutationParams {
+    pub add_prob: f32,               // Chance to grow a new cell
+    pub change_prob: f32,            // Chance to change the type of a cell
+    pub remove_prob: f32,            // Chance to lose a cell
+    pub move_range_mutate_prob: f32, // Chance to nudge the move range
+    pub mutability_mutate_prob: f32, // Chance to nudge the mutability itself
+    pub brain_mutate_prob: f32,      // Chance to re-roll one reaction of an inherited brain
+}
+
+impl Default for MutationParams {
+    fn default() -> Self {
+        MutationParams {
+            add_prob: 33.0,
+            change_prob: 33.0,
+            remove_prob: 33.0,
+            move_range_mutate_prob: 10.0,
+            mutability_mutate_prob: 10.0,
+            brain_mutate_prob: 10.0,
+        }
+    }
+}
+
 /// Represents a collection of cells that form a living organism
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Organism {
     pub id: usize,              // Unique identifier
     pub x: u32,                 // Position X
@@ -113,6 +166,18 @@ pub struct Organism {
     pub move_range: u32,        // How many steps in one direction before changing
     pub move_counter: u32,      // Counter for current movement
     pub is_alive: bool,         // Whether the organism is alive
+    pub energy: u32,            // Energy spent producing food and moving
+    pub species: u64,           // Anatomy hash, shared by organisms with identical bodies
+    pub bounds: (i32, i32, i32, i32), // Unrotated (min_x, min_y, max_x, max_y) of the cells; updated with `species`
+    pub death_cause: Option<DeathCause>, // Why the organism died, once it has
+    pub parent_id: Option<usize>, // ID of the organism this one was born from, if any
+    pub kills: u32,             // Organisms this one dealt the lethal blow to
+    pub total_food_eaten: u32,  // Lifetime food eaten; unlike food_collected, never spent
+    pub move_food_debt: f32,    // Fraction of a food owed for moving, paid once it adds up to a whole one
+    pub hunger: u32,            // Steps in a row it couldn't pay to move and had no food at its mouth
+    pub max_lifetime: u32,      // Steps it may live, fixed from its size when placed on a grid (0 = not yet)
+    pub steps_since_reproduction: u32, // Steps since its last birth, or since it was born if it has none
+    pub brain: Option<Brain>,   // Reactions to what the eyes see; None follows `decide_move`'s built-in rules
 
 }
 
@@ -124,7 +189,7 @@ impl Organism {
             x,
             y,
             rotation: Direction::Up,
-            move_direction: Direction::random(),
+            move_direction: Direction::Up,
             cells: Vec::new(),
             food_collected: 0,
             health: 0,
@@ -133,22 +198,37 @@ impl Organism {
             move_range: 4,  // Move 4 steps before changing direction
             move_counter: 0,
             is_alive: true,
+            energy: 0,
+            species: 0,
+            bounds: (0, 0, 0, 0),
+            death_cause: None,
+            parent_id: None,
+            kills: 0,
+            total_food_eaten: 0,
+            move_food_debt: 0.0,
+            hunger: 0,
+            max_lifetime: 0,
+            steps_since_reproduction: 0,
+            brain: None,
         };
         
         // Add a default mouth cell at the center
-        organism.add_cell(CellStates::Mouth, 0, 0);
+        organism.add_cell(CellState::Mouth, 0, 0);
+        organism.energy = organism.max_energy();
         
         organism
     }
     
-    /// Create a new organism from a parent (with possible mutations)
-    pub fn new_from_parent(id: usize, x: u32, y: u32, parent: &Organism) -> Self {
+    /// Create a new organism from a parent (with possible mutations).
+    /// Mutation never grows the body past `max_cells` cells (0 means no limit).
+    pub fn new_from_parent(id: usize, x: u32, y: u32, parent: &Organism,
+                           mutation: &MutationParams, max_cells: usize, rng: &mut impl Rng) -> Self {
         let mut organism = Organism {
             id,
             x,
             y,
-            rotation: Direction::random(), // Random rotation for offspring
-            move_direction: Direction::random(),
+            rotation: Direction::random(rng), // Random rotation for offspring
+            move_direction: Direction::random(rng),
             cells: parent.cells.clone(),
             food_collected: 0,
             health: 0,
@@ -157,33 +237,56 @@ impl Organism {
             move_range: parent.move_range,  // Inherit move range
             move_counter: 0,
             is_alive: true,
+            energy: 0,
+            species: parent.species,
+            bounds: parent.bounds,
+            death_cause: None,
+            parent_id: Some(parent.id),
+            kills: 0,
+            total_food_eaten: 0,
+            move_food_debt: 0.0,
+            hunger: 0,
+            max_lifetime: 0,
+            steps_since_reproduction: 0,
+            brain: parent.brain,  // Inherit the personality
         };
         
         // Mutate with probability based on mutability
-        let mut rng = rand::thread_rng();
         if rng.gen_range(0..100) < organism.mutability {
-            organism.mutate();
+            organism.mutate(mutation, max_cells, rng);
             
             // Also sometimes mutate the move_range
-            if rng.gen_range(0..100) < 10 {
+            if rng.gen::<f32>() * 100.0 < mutation.move_range_mutate_prob {
                 organism.move_range = (organism.move_range as i32 + rng.gen_range(-2..3))
                     .max(1) as u32; // Ensure move_range is at least 1
             }
             
             // And sometimes mutate the mutability itself
-            if rng.gen_range(0..100) < 10 {
+            if rng.gen::<f32>() * 100.0 < mutation.mutability_mutate_prob {
                 organism.mutability = (organism.mutability as i32 + rng.gen_range(-1..2))
-                    .max(1).min(100) as u8;
+                    .clamp(1, 100) as u8;
+            }
+            
+            // And sometimes change how it reacts to one kind of cell
+            if let Some(brain) = &mut organism.brain {
+                if rng.gen::<f32>() * 100.0 < mutation.brain_mutate_prob {
+                    brain.mutate(rng);
+                }
             }
         }
         
+        // Offspring are born with full energy
+        organism.energy = organism.max_energy();
+        
         organism
     }
     
-    /// Add a cell to the organism
-    pub fn add_cell(&mut self, state: CellStates, x: i32, y: i32) {
+    /// Add a cell to the organism. Stored energy is left alone, so a body built up cell by
+    /// cell should be topped up with `max_energy()` once it's finished.
+    pub fn add_cell(&mut self, state: CellState, x: i32, y: i32) {
         self.cells.push(OrganismCell::new(state, x, y));
         self.health = self.cells.len() as u32; // Health equals number of cells
+        self.anatomy_changed();
     }
     
     /// Check if we can add a cell at the specific relative position
@@ -191,48 +294,215 @@ impl Organism {
         !self.cells.iter().any(|cell| cell.x == x && cell.y == y)
     }
     
-    /// Get the absolute position of a cell in the grid
-    pub fn get_cell_position(&self, cell: &OrganismCell) -> (u32, u32) {
+    /// Stable hash of the body layout: the sorted set of (x, y, state) cells.
+    /// Rotation, position and eye directions don't affect it.
+    pub fn anatomy_hash(&self) -> u64 {
+        let mut layout: Vec<(i32, i32, u8)> = self.cells.iter()
+            .map(|cell| (cell.x, cell.y, cell.state as u8))
+            .collect();
+        layout.sort_unstable();
+        
+        // FNV-1a, so the hash is the same across runs, platforms and Rust versions
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for (x, y, state) in layout {
+            for byte in x.to_le_bytes().iter().chain(y.to_le_bytes().iter()).chain([state].iter()) {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x100000001b3);
+            }
+        }
+        hash
+    }
+    
+    /// Whether removing `cells[idx]` leaves every cell that was 4-connected to the center still connected
+    fn stays_connected_without(&self, idx: usize) -> bool {
+        let before = self.connected_to_center(None);
+        let after = self.connected_to_center(Some(idx));
+        after + 1 >= before
+    }
+    
+    /// Number of cells 4-connected to the center cell, optionally ignoring one cell
+    fn connected_to_center(&self, skip: Option<usize>) -> usize {
+        let mut reached = vec![false; self.cells.len()];
+        let mut stack: Vec<usize> = self.cells.iter()
+            .position(|cell| cell.x == 0 && cell.y == 0)
+            .into_iter()
+            .collect();
+        
+        while let Some(i) = stack.pop() {
+            if Some(i) == skip || reached[i] {
+                continue;
+            }
+            reached[i] = true;
+            let (x, y) = (self.cells[i].x, self.cells[i].y);
+            for (j, other) in self.cells.iter().enumerate() {
+                if !reached[j] && (other.x - x).abs() + (other.y - y).abs() == 1 {
+                    stack.push(j);
+                }
+            }
+        }
+        
+        reached.iter().filter(|&&r| r).count()
+    }
+    
+    /// Get the absolute position of a cell in the grid, or `None` if it lies left of or above
+    /// the origin. Positions past the right or bottom edge are left for the grid to check.
+    pub fn get_cell_position(&self, cell: &OrganismCell) -> Option<(u32, u32)> {
         let (dx, dy) = cell.get_rotated_position(self.rotation);
-        ((self.x as i32 + dx) as u32, (self.y as i32 + dy) as u32)
+        Some((self.x.checked_add_signed(dx)?, self.y.checked_add_signed(dy)?))
+    }
+    
+    /// Recompute `species` and `bounds` after the cells change
+    pub(crate) fn anatomy_changed(&mut self) {
+        self.species = self.anatomy_hash();
+        let mut cells = self.cells.iter();
+        self.bounds = match cells.next() {
+            Some(first) => cells.fold((first.x, first.y, first.x, first.y), |(min_x, min_y, max_x, max_y), cell| {
+                (min_x.min(cell.x), min_y.min(cell.y), max_x.max(cell.x), max_y.max(cell.y))
+            }),
+            None => (0, 0, 0, 0),
+        };
+    }
+    
+    /// Smallest box around the cells at the current rotation, as (min_x, min_y, max_x, max_y)
+    /// offsets from the center. Read from the cached `bounds`, so it costs the same for any size.
+    ///
+    /// ```
+    /// use simulation::{CellState, Direction, Organism};
+    ///
+    /// let mut organism = Organism::new(0, 10, 10);
+    /// organism.add_cell(CellState::Producer, 1, 1);
+    /// organism.add_cell(CellState::Mover, -2, 0);
+    /// assert_eq!(organism.bounding_box(), (-2, 0, 1, 1));
+    ///
+    /// // Right turns (x, y) into (y, -x)
+    /// organism.rotation = Direction::Right;
+    /// assert_eq!(organism.bounding_box(), (0, -1, 1, 2));
+    /// ```
+    pub fn bounding_box(&self) -> (i32, i32, i32, i32) {
+        let (min_x, min_y, max_x, max_y) = self.bounds;
+        match self.rotation {
+            Direction::Up => (min_x, min_y, max_x, max_y),
+            Direction::Right => (min_y, -max_x, max_y, -min_x),
+            Direction::Down => (-max_x, -max_y, -min_x, -min_y),
+            Direction::Left => (-max_y, min_x, -min_y, max_x),
+        }
+    }
+    
+    /// Offsets of every cell from the center at the current rotation, in cell order.
+    /// Compute once and reuse when checking the same body at several positions.
+    pub fn footprint(&self) -> Vec<(i32, i32)> {
+        self.cells.iter().map(|cell| cell.get_rotated_position(self.rotation)).collect()
     }
     
     /// Check if this organism has eyes
     pub fn has_eyes(&self) -> bool {
-        self.cells.iter().any(|cell| cell.state == CellStates::Eye)
+        self.cells.iter().any(|cell| cell.state == CellState::Eye)
     }
     
     /// Check if this organism has mover cells
     pub fn has_movers(&self) -> bool {
-        self.cells.iter().any(|cell| cell.state == CellStates::Mover)
+        self.cells.iter().any(|cell| cell.state == CellState::Mover)
     }
     
     /// Check if this organism has producer cells
     pub fn has_producers(&self) -> bool {
-        self.cells.iter().any(|cell| cell.state == CellStates::Producer)
+        self.cells.iter().any(|cell| cell.state == CellState::Producer)
+    }
+    
+    /// Every `heal_interval` steps of life, regain 1 health per heal cell,
+    /// never above one health per cell. An interval of 0 disables healing.
+    pub fn heal(&mut self, heal_interval: u32) {
+        if heal_interval == 0 || self.lifetime == 0 || !self.lifetime.is_multiple_of(heal_interval) {
+            return;
+        }
+        let healers = self.cells.iter().filter(|cell| cell.state == CellState::Heal).count() as u32;
+        self.health = (self.health + healers).min(self.cells.len() as u32);
     }
     
-    /// Get the amount of food needed to reproduce
-    pub fn food_needed_to_reproduce(&self) -> u32 {
-        if self.has_movers() {
-            // In JS: this.anatomy.cells.length + Hyperparams.extraMoverFoodCost
-            self.cells.len() as u32 + 1
+    /// Check if this organism has killer cells
+    pub fn has_killers(&self) -> bool {
+        self.cells.iter().any(|cell| cell.state == CellState::Killer)
+    }
+    
+    /// Get the amount of food needed to reproduce: one per cell, plus `extra_mover_food_cost`
+    /// for movers, all scaled by `cost_multiplier` and rounded up. Never less than 1.
+    pub fn food_needed_to_reproduce(&self, extra_mover_food_cost: u32, cost_multiplier: f32) -> u32 {
+        // In JS: this.anatomy.cells.length + Hyperparams.extraMoverFoodCost
+        let base = if self.has_movers() {
+            self.cells.len() as u32 + extra_mover_food_cost
         } else {
             self.cells.len() as u32
+        };
+        ((base as f32 * cost_multiplier).ceil() as u32).max(1)
+    }
+    
+    /// Get the most energy this organism can store
+    pub fn max_energy(&self) -> u32 {
+        self.cells.len() as u32 * ENERGY_PER_CELL
+    }
+    
+    /// Stored energy as a fraction of the maximum (0.0-1.0), e.g. for highlighting starving organisms
+    pub fn energy_fraction(&self) -> f32 {
+        let max = self.max_energy();
+        if max == 0 {
+            0.0
+        } else {
+            (self.energy as f32 / max as f32).min(1.0)
         }
     }
     
-    /// Get the maximum lifespan of this organism
+    /// Regain some energy, up to the maximum
+    pub fn regenerate_energy(&mut self, amount: u32) {
+        self.energy = self.energy.saturating_add(amount).min(self.max_energy());
+    }
+    
+    /// Pay `upkeep_per_cell` energy for every cell, starving once the store runs dry
+    pub fn metabolize(&mut self, upkeep_per_cell: u32) {
+        let upkeep = self.cells.len() as u32 * upkeep_per_cell;
+        if self.energy <= upkeep {
+            self.energy = 0;
+            self.die(DeathCause::Starved);
+        } else {
+            self.energy -= upkeep;
+        }
+    }
+    
+    /// Get the maximum lifespan of this organism: `max_lifetime` once fixed,
+    /// otherwise what its current size would give
+    ///
+    /// ```
+    /// use simulation::{CellState, Grid};
+    ///
+    /// let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+    /// grid.lifespan_multiplier = 10;
+    /// grid.create_basic_organism(10, 10);
+    /// // Growing after birth doesn't buy more time
+    /// grid.organisms[0].add_cell(CellState::Producer, 1, -1);
+    /// assert_eq!(grid.organisms[0].max_lifespan(grid.lifespan_multiplier), 30);
+    /// ```
     pub fn max_lifespan(&self, lifespan_multiplier: u32) -> u32 {
+        if self.max_lifetime > 0 {
+            return self.max_lifetime;
+        }
         (self.cells.len() as u32 * lifespan_multiplier).max(1)
     }
     
-    /// Try to reproduce (returns a new organism if successful)
-    pub fn try_reproduce(&mut self) -> Option<Organism> {
-        if self.food_collected >= self.food_needed_to_reproduce() {
-            // Reduce the food collected
-            self.food_collected -= self.food_needed_to_reproduce();
-            
+    /// Age as a fraction of the maximum lifespan (0.0 at birth, 1.0 at death from old age)
+    pub fn lifetime_fraction(&self, lifespan_multiplier: u32) -> f32 {
+        (self.lifetime as f32 / self.max_lifespan(lifespan_multiplier) as f32).min(1.0)
+    }
+    
+    /// Try to reproduce (returns a new organism if successful).
+    /// The offspring never grows past `max_cells` cells through mutation (0 means no limit).
+    /// The food it costs is `food_needed_to_reproduce(extra_mover_food_cost, cost_multiplier)`.
+    /// With `deterministic` set the birth spot is always the first direction (Up) at the
+    /// birth distance, so it depends only on the parent's body and position.
+    /// The parent isn't charged here; call `record_birth` once the offspring is placed.
+    pub fn try_reproduce(&self, mutation: &MutationParams, max_cells: usize,
+                         extra_mover_food_cost: u32, cost_multiplier: f32,
+                         deterministic: bool, rng: &mut impl Rng) -> Option<Organism> {
+        let food_needed = self.food_needed_to_reproduce(extra_mover_food_cost, cost_multiplier);
+        if self.food_collected >= food_needed {
             // Try more directions including diagonals with more sophisticated positioning
             let directions = [
                 (0, -1),   // Up
@@ -245,178 +515,281 @@ impl Organism {
                 (-1, -1)   // Up-Left
             ];
             
-            let mut rng = rand::thread_rng();
+            // Pick a birth direction at random (or the first one when placement is
+            // deterministic); position checking is done at grid level
+            let (dx, dy) = if deterministic {
+                directions[0]
+            } else {
+                directions[rng.gen_range(0..directions.len())]
+            };
+            let birth_distance = self.calculate_birth_distance();
             
-            // Randomize direction order manually
-            let mut randomized_directions = directions.to_vec();
-            for i in 0..randomized_directions.len() {
-                let swap_idx = rng.gen_range(0..randomized_directions.len());
-                randomized_directions.swap(i, swap_idx);
-            }
+            // More sophisticated distance calculation with randomness
+            let rand_offset: i32 = if deterministic { 0 } else { rng.gen_range(0..3) }; // Random offset 0-2
+            let offset_x = dx * (birth_distance + rand_offset);
+            let offset_y = dy * (birth_distance + rand_offset);
             
-            // Try each direction to find a suitable spot
-            for &(dx, dy) in &randomized_directions {
-                let birth_distance = self.calculate_birth_distance();
-                
-                // More sophisticated distance calculation with randomness
-                let rand_offset = rng.gen_range(0..3) as i32; // Random offset 0-2
-                let offset_x = dx * (birth_distance + rand_offset);
-                let offset_y = dy * (birth_distance + rand_offset);
-                
-                let new_x = (self.x as i32 + offset_x).max(0) as u32;
-                let new_y = (self.y as i32 + offset_y).max(0) as u32;
-                
-                // Create offspring at this position
-                let mut offspring = Organism::new_from_parent(0, new_x, new_y, self);
-                
-                // Optionally adjust offspring rotation based on parent's movement
-                if rng.gen_bool(0.5) {
-                    // Option 1: Inherit parent's current rotation
-                    offspring.rotation = self.rotation;
-                } else if rng.gen_bool(0.5) {
-                    // Option 2: Rotate towards the birth direction
-                    offspring.rotation = match (dx, dy) {
-                        (0, -1) => Direction::Up,
-                        (1, 0) => Direction::Right,
-                        (0, 1) => Direction::Down,
-                        (-1, 0) => Direction::Left,
-                        (1, -1) => Direction::Up,   // Bias towards Up for diagonal
-                        (1, 1) => Direction::Down,  // Bias towards Down for diagonal
-                        (-1, 1) => Direction::Down, // Bias towards Down for diagonal
-                        (-1, -1) => Direction::Up,  // Bias towards Up for diagonal
-                        _ => Direction::random(),
-                    };
-                } else {
-                    // Option 3: Completely random rotation
-                    offspring.rotation = Direction::random();
-                }
-                
-                // Return the offspring - position checking will be done at grid level
-                return Some(offspring);
+            let new_x = (self.x as i32 + offset_x).max(0) as u32;
+            let new_y = (self.y as i32 + offset_y).max(0) as u32;
+            
+            // Create offspring at this position
+            let mut offspring = Organism::new_from_parent(0, new_x, new_y, self, mutation, max_cells, rng);
+            
+            // Optionally adjust offspring rotation based on parent's movement
+            if rng.gen_bool(0.5) {
+                // Option 1: Inherit parent's current rotation
+                offspring.rotation = self.rotation;
+            } else if rng.gen_bool(0.5) {
+                // Option 2: Rotate towards the birth direction
+                offspring.rotation = match (dx, dy) {
+                    (0, -1) => Direction::Up,
+                    (1, 0) => Direction::Right,
+                    (0, 1) => Direction::Down,
+                    (-1, 0) => Direction::Left,
+                    (1, -1) => Direction::Up,   // Bias towards Up for diagonal
+                    (1, 1) => Direction::Down,  // Bias towards Down for diagonal
+                    (-1, 1) => Direction::Down, // Bias towards Down for diagonal
+                    (-1, -1) => Direction::Up,  // Bias towards Up for diagonal
+                    _ => Direction::random(rng),
+                };
+            } else {
+                // Option 3: Completely random rotation
+                offspring.rotation = Direction::random(rng);
             }
             
-            // If we get here, all directions failed
-            None
+            Some(offspring)
         } else {
             None
         }
     }
 
+    /// Pay for an offspring that made it onto the grid: spend the food it cost and restart
+    /// the reproduction cooldown
+    pub fn record_birth(&mut self, extra_mover_food_cost: u32, cost_multiplier: f32) {
+        let food_needed = self.food_needed_to_reproduce(extra_mover_food_cost, cost_multiplier);
+        self.food_collected = self.food_collected.saturating_sub(food_needed);
+        self.steps_since_reproduction = 0;
+    }
+
 fn calculate_birth_distance(&self) -> i32 {
     // Find the maximum extent of the organism in any direction
-    let mut max_extent = 0;
-    for cell in &self.cells {
-        let extent = cell.x.abs().max(cell.y.abs());
-        max_extent = max_extent.max(extent);
-    }
+    let (min_x, min_y, max_x, max_y) = self.bounds;
+    let max_extent = min_x.abs().max(min_y.abs()).max(max_x).max(max_y);
     
     // Birth distance needs to be at least the max extent plus a buffer
-    (max_extent + 3) as i32
+    max_extent + 3
 }
     
-    /// Mutate this organism by adding, changing, or removing a cell
-    pub fn mutate(&mut self) -> bool {
+    /// Mutate this organism by adding, changing, or removing a cell.
+    /// No cell is added once the body has `max_cells` cells (0 means no limit).
+    pub fn mutate(&mut self, mutation: &MutationParams, max_cells: usize, rng: &mut impl Rng) -> bool {
         let mut changed = false;
-        
-        // Get probabilities from settings
-        let add_prob = 33; // This should be configurable
-        let change_prob = 33; // This should be configurable
-        let remove_prob = 33; // This should be configurable
+        let at_cap = max_cells > 0 && self.cells.len() >= max_cells;
         
         // Try to add a cell
-        if random::<f32>() * 100.0 < add_prob as f32 {
-            // ... existing code for adding cells
-            changed = true;
+        if rng.gen::<f32>() * 100.0 < mutation.add_prob && !at_cap {
+            // Grow into a free slot next to a random existing cell, keeping the body connected
+            let anchor = &self.cells[rng.gen_range(0..self.cells.len())];
+            let free_slots: Vec<(i32, i32)> = [(0, -1), (1, 0), (0, 1), (-1, 0)].iter()
+                .map(|(dx, dy)| (anchor.x + dx, anchor.y + dy))
+                .filter(|&(x, y)| self.can_add_cell_at(x, y))
+                .collect();
+            
+            if !free_slots.is_empty() {
+                let (x, y) = free_slots[rng.gen_range(0..free_slots.len())];
+                let state = random_cell_state(rng);
+                self.add_cell(state, x, y);
+                // New eyes look in a random direction
+                if state == CellState::Eye {
+                    if let Some(cell) = self.cells.last_mut() {
+                        cell.direction = Some(Direction::random(rng));
+                    }
+                }
+                changed = true;
+            }
         }
         
         // Try to change a cell type
-        if random::<f32>() * 100.0 < change_prob as f32 {
-            if self.cells.len() > 1 { // Protect the center cell
-                let idx = (random::<f32>() * (self.cells.len() - 1) as f32) as usize + 1;
-                // Make sure we get a cell different from the current one
-                let mut new_state = random_cell_state();
-                while new_state == self.cells[idx].state {
-                    new_state = random_cell_state();
-                }
-                self.cells[idx].state = new_state;
-                changed = true;
+        if rng.gen::<f32>() * 100.0 < mutation.change_prob && self.cells.len() > 1 { // Protect the center cell
+            let idx = self.random_non_center_cell(rng);
+            // Make sure we get a cell different from the current one
+            let mut new_state = random_cell_state(rng);
+            while new_state == self.cells[idx].state {
+                new_state = random_cell_state(rng);
             }
+            self.cells[idx].state = new_state;
+            // Only eyes carry a facing direction
+            self.cells[idx].direction = if new_state == CellState::Eye {
+                Some(Direction::random(rng))
+            } else {
+                None
+            };
+            changed = true;
         }
         
         // Try to remove a cell
-        if random::<f32>() * 100.0 < remove_prob as f32 {
-            if self.cells.len() > 1 { // Don't remove the last cell
-                let idx = (random::<f32>() * (self.cells.len() - 1) as f32) as usize + 1;
-                // Don't remove center cell
-                if self.cells[idx].x != 0 || self.cells[idx].y != 0 {
-                    self.cells.remove(idx);
-                    changed = true;
-                }
+        if rng.gen::<f32>() * 100.0 < mutation.remove_prob && self.cells.len() > 1 { // Don't remove the last cell
+            let idx = self.random_non_center_cell(rng);
+            // Don't remove a cell holding the body together
+            if self.stays_connected_without(idx) {
+                self.cells.remove(idx);
+                self.health = self.cells.len() as u32;
+                changed = true;
             }
         }
         
-        return changed;
+        if changed {
+            self.anatomy_changed();
+        }
+        changed
     }
     
-    /// Try to move in the current direction
-    pub fn try_move(&mut self, grid_width: u32, grid_height: u32, 
-                   is_position_clear: impl Fn(u32, u32) -> bool) -> bool {
+    /// Index of a random cell other than the center, wherever the center sits in `cells`
+    /// (imported bodies keep their input order). The body must have more than one cell.
+    fn random_non_center_cell(&self, rng: &mut impl Rng) -> usize {
+        let others: Vec<usize> = (0..self.cells.len())
+            .filter(|&idx| self.cells[idx].x != 0 || self.cells[idx].y != 0)
+            .collect();
+        others[((rng.gen::<f32>() * others.len() as f32) as usize).min(others.len() - 1)]
+    }
+    
+    /// Whole food a move costing `move_food_cost` would take now, counting fractions owed
+    fn move_food_due(&self, move_food_cost: f32) -> u32 {
+        (self.move_food_debt + move_food_cost).floor() as u32
+    }
+    
+    /// Whether the collected food covers a move costing `move_food_cost`
+    pub fn can_afford_move(&self, move_food_cost: f32) -> bool {
+        self.move_food_due(move_food_cost) <= self.food_collected
+    }
+    
+    /// Try to move in the current direction.
+    /// With `wrap_edges` the organism wraps around the grid; without it a move that would
+    /// take any of its cells off the grid is refused.
+    /// Each successful move costs `move_energy_cost` energy and `move_food_cost` collected food;
+    /// fractions of a food are owed until they add up. A move blocked by a wall or another
+    /// organism costs nothing. Once `move_range` steps are up
+    /// the next direction is `preferred` if given, otherwise random.
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_move(&mut self, grid_width: u32, grid_height: u32, wrap_edges: bool,
+                   is_position_clear: impl Fn(u32, u32) -> bool,
+                   move_energy_cost: u32,
+                   move_food_cost: f32,
+                   preferred: Option<Direction>,
+                   rng: &mut impl Rng) -> bool {
         // Only organisms with mover cells can move
         if !self.has_movers() {
             return false;
         }
         
+        // Too tired or too hungry to move
+        if self.energy < move_energy_cost || !self.can_afford_move(move_food_cost) {
+            return false;
+        }
+        
+        // Bring a coordinate back onto the grid, or give None where it falls off
+        let fit = |v: i32, size: u32| -> Option<u32> {
+            if wrap_edges {
+                Some(v.rem_euclid(size as i32) as u32)
+            } else {
+                (v >= 0 && v < size as i32).then_some(v as u32)
+            }
+        };
+        
         let (dx, dy) = self.move_direction.to_delta();
-        let new_x = (self.x as i32 + dx).max(0).min(grid_width as i32 - 1) as u32;
-        let new_y = (self.y as i32 + dy).max(0).min(grid_height as i32 - 1) as u32;
+        let (new_x, new_y) = match (fit(self.x as i32 + dx, grid_width), fit(self.y as i32 + dy, grid_height)) {
+            (Some(x), Some(y)) => (x, y),
+            // Pressed against a wall the center would leave the grid, which is no move at all
+            _ => (self.x, self.y),
+        };
         
-        // Check if all cells can move to their new positions
-        let can_move = self.cells.iter().all(|cell| {
+        // Check if all cells can move to their new positions, all of them staying on the grid
+        let can_move = (new_x, new_y) != (self.x, self.y) && self.cells.iter().all(|cell| {
             let (cell_dx, cell_dy) = cell.get_rotated_position(self.rotation);
-            let cell_x = (new_x as i32 + cell_dx).max(0).min(grid_width as i32 - 1) as u32;
-            let cell_y = (new_y as i32 + cell_dy).max(0).min(grid_height as i32 - 1) as u32;
+            let (cell_x, cell_y) = match (fit(new_x as i32 + cell_dx, grid_width),
+                                          fit(new_y as i32 + cell_dy, grid_height)) {
+                (Some(x), Some(y)) => (x, y),
+                _ => return false, // Off the grid
+            };
             
             // Check if the new position is clear (or belongs to this organism)
-            let current_pos = self.get_cell_position(cell);
-            (cell_x, cell_y) == current_pos || is_position_clear(cell_x, cell_y)
+            let current_pos = fit(self.x as i32 + cell_dx, grid_width).zip(fit(self.y as i32 + cell_dy, grid_height));
+            current_pos == Some((cell_x, cell_y)) || is_position_clear(cell_x, cell_y)
         });
         
         if can_move {
             self.x = new_x;
             self.y = new_y;
+            self.energy -= move_energy_cost;
+            let food_due = self.move_food_due(move_food_cost);
+            self.move_food_debt += move_food_cost - food_due as f32;
+            self.food_collected -= food_due;
             self.move_counter += 1;
             
             // Change direction after move_range steps
             if self.move_counter >= self.move_range {
-                self.move_direction = Direction::random();
+                self.move_direction = preferred.unwrap_or_else(|| Direction::random(rng));
                 self.move_counter = 0;
             }
             
             true
         } else {
             // If blocked, we might want to change direction
-            if rand::thread_rng().gen_bool(0.5) {
-                self.move_direction = Direction::random();
+            if rng.gen_bool(0.5) {
+                self.move_direction = Direction::random(rng);
                 self.move_counter = 0;
             }
             false
         }
     }
     
-    /// Try to rotate to a new orientation
-    pub fn try_rotate(&mut self, 
-                     is_position_clear: impl Fn(u32, u32) -> bool) -> bool {
-        let new_rotation = Direction::random();
+    /// Try to rotate to a random orientation
+    pub fn try_rotate(&mut self, grid_width: u32, grid_height: u32, wrap_edges: bool,
+                     is_position_clear: impl Fn(u32, u32) -> bool,
+                     rng: &mut impl Rng) -> bool {
+        self.try_rotate_to(Direction::random(rng), grid_width, grid_height, wrap_edges, is_position_clear)
+    }
+    
+    /// Try to rotate to face `new_rotation`. Without `wrap_edges` a rotation that would
+    /// swing any cell off the grid is refused; with it, cells wrap like they do in `try_move`.
+    ///
+    /// ```
+    /// use simulation::{CellState, Direction, Organism};
+    ///
+    /// // Hugging the bottom edge of a 10x10 grid, with a cell to its right
+    /// let mut organism = Organism::new(1, 5, 9);
+    /// organism.add_cell(CellState::Producer, 1, 0);
+    /// let everything_clear = |_, _| true;
+    /// // Facing left would swing that cell below the grid
+    /// assert!(!organism.try_rotate_to(Direction::Left, 10, 10, false, everything_clear));
+    /// assert_eq!(organism.rotation, Direction::Up);
+    /// // Facing down swings it to the left, still on the bottom row
+    /// assert!(organism.try_rotate_to(Direction::Down, 10, 10, false, everything_clear));
+    /// ```
+    pub fn try_rotate_to(&mut self, new_rotation: Direction, grid_width: u32, grid_height: u32,
+                         wrap_edges: bool, is_position_clear: impl Fn(u32, u32) -> bool) -> bool {
+        // Bring a coordinate back onto the grid, or give None where it falls off
+        let fit = |v: i32, size: u32| -> Option<u32> {
+            if wrap_edges {
+                Some(v.rem_euclid(size as i32) as u32)
+            } else {
+                (v >= 0 && v < size as i32).then_some(v as u32)
+            }
+        };
         
         // Check if all cells can be in their new rotated positions
         let can_rotate = self.cells.iter().all(|cell| {
             let (cell_dx, cell_dy) = cell.get_rotated_position(new_rotation);
-            let cell_x = (self.x as i32 + cell_dx).max(0) as u32;
-            let cell_y = (self.y as i32 + cell_dy).max(0) as u32;
+            let (cell_x, cell_y) = match (fit(self.x as i32 + cell_dx, grid_width),
+                                          fit(self.y as i32 + cell_dy, grid_height)) {
+                (Some(x), Some(y)) => (x, y),
+                _ => return false, // Off the grid
+            };
             
-            // Check if the new position is clear
-            let current_pos = self.get_cell_position(cell);
-            (cell_x, cell_y) == current_pos || is_position_clear(cell_x, cell_y)
+            // Check if the new position is clear (or belongs to this organism)
+            let (dx, dy) = cell.get_rotated_position(self.rotation);
+            let current_pos = fit(self.x as i32 + dx, grid_width).zip(fit(self.y as i32 + dy, grid_height));
+            current_pos == Some((cell_x, cell_y)) || is_position_clear(cell_x, cell_y)
         });
         
         if can_rotate {
@@ -433,67 +806,942 @@ fn calculate_birth_distance(&self) -> i32 {
             self.health -= 1;
         }
         if self.health == 0 {
+            self.die(DeathCause::Killed);
+        }
+    }
+    
+    /// Fitness score used to rank organisms: food eaten plus kills
+    pub fn fitness(&self) -> u32 {
+        self.total_food_eaten.saturating_add(self.kills)
+    }
+    
+    /// Mark the organism as dead. The first cause recorded is kept.
+    pub fn die(&mut self, cause: DeathCause) {
+        if self.is_alive {
             self.is_alive = false;
+            self.death_cause = Some(cause);
         }
     }
     
-    /// Update the organism for one time step
-    pub fn update(&mut self, grid_width: u32, grid_height: u32,
+    /// Cast a ray from each eye in its absolute facing direction and return the
+    /// direction and state of the nearest cell seen within `view_distance` tiles.
+    /// Empty cells and the organism's own cells are looked through.
+    pub fn look(&self, view_distance: u32,
+                cell_at: impl Fn(u32, u32) -> CellState) -> Option<(Direction, CellState)> {
+        let own_positions: Vec<(u32, u32)> = self.cells.iter()
+            .filter_map(|cell| self.get_cell_position(cell))
+            .collect();
+        let mut nearest: Option<(u32, Direction, CellState)> = None;
+        
+        for cell in &self.cells {
+            if cell.state != CellState::Eye {
+                continue;
+            }
+            let direction = match cell.get_absolute_direction(self.rotation) {
+                Some(direction) => direction,
+                None => continue,
+            };
+            
+            let (ex, ey) = match self.get_cell_position(cell) {
+                Some(pos) => pos,
+                None => continue, // Off the grid
+            };
+            let (dx, dy) = direction.to_delta();
+            
+            for distance in 1..=view_distance {
+                // Stop once a closer sighting has already been made by another eye
+                if nearest.is_some_and(|(d, _, _)| distance >= d) {
+                    break;
+                }
+                let x = ex as i32 + dx * distance as i32;
+                let y = ey as i32 + dy * distance as i32;
+                if x < 0 || y < 0 {
+                    break;
+                }
+                let (x, y) = (x as u32, y as u32);
+                if own_positions.contains(&(x, y)) {
+                    continue;
+                }
+                
+                let state = cell_at(x, y);
+                if state != CellState::Empty {
+                    nearest = Some((distance, direction, state));
+                    break;
+                }
+            }
+        }
+        
+        nearest.map(|(_, direction, state)| (direction, state))
+    }
+    
+    /// Pick a direction from what the eyes saw (the result of `look`). An organism with a
+    /// brain chases, flees or ignores as its table says. Without one: towards food,
+    /// away from killers and hazards, and for organisms with their own killer cells, towards prey.
+    /// `None` when nothing interesting is in sight.
+    ///
+    /// ```
+    /// use simulation::{Brain, CellState, Direction, Organism, Reaction};
+    ///
+    /// let mut organism = Organism::new(1, 10, 10);
+    /// let mut brain = Brain::default();
+    /// brain.set_reaction(CellState::Killer, Reaction::Flee);
+    /// brain.set_reaction(CellState::Food, Reaction::Chase);
+    /// organism.brain = Some(brain);
+    /// assert_eq!(organism.decide_move(Some((Direction::Left, CellState::Killer))), Some(Direction::Right));
+    /// assert_eq!(organism.decide_move(Some((Direction::Up, CellState::Food))), Some(Direction::Up));
+    /// assert_eq!(organism.decide_move(Some((Direction::Up, CellState::Wall))), None);
+    /// ```
+    pub fn decide_move(&self, sighting: Option<(Direction, CellState)>) -> Option<Direction> {
+        let (direction, state) = sighting?;
+        if let Some(brain) = &self.brain {
+            return match brain.reaction(state) {
+                Reaction::Chase => Some(direction),
+                Reaction::Flee => Some(direction.opposite()),
+                Reaction::Ignore => None,
+            };
+        }
+        match state {
+            CellState::Food => Some(direction),
+            CellState::Killer | CellState::Hazard => Some(direction.opposite()),
+            CellState::Empty | CellState::Wall => None,
+            _ if self.has_killers() => Some(direction), // Another organism's body
+            _ => None,
+        }
+    }
+    
+    /// Head the way `decide_move` picked, if anywhere
+    fn steer(&mut self, decision: Option<Direction>) {
+        let new_direction = match decision {
+            Some(direction) => direction,
+            None => return,
+        };
+        
+        if new_direction != self.move_direction {
+            self.move_direction = new_direction;
+            self.move_counter = 0;
+        }
+    }
+    
+    /// Count the food cells in the square of radius `r` around the organism's center
+    pub fn food_in_radius(&self, r: u32, food_at: impl Fn(u32, u32) -> bool) -> u32 {
+        let r = r as i32;
+        let mut count = 0;
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let pos = self.x.checked_add_signed(dx).zip(self.y.checked_add_signed(dy));
+                if pos.is_some_and(|(x, y)| food_at(x, y)) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+    
+    /// Update the organism for one time step.
+    /// `sighting` is the result of `look` taken against the grid before anything moved.
+    /// `food_density` is the fraction of nearby cells holding food, for movers that smell
+    /// rather than see; they stay put with that chance instead of roaming.
+    /// `food_direction` is where the most food lies, taken as the next heading when one is due.
+    /// Eating is handled by the grid, which removes the food it counts.
+    /// Returns what the organism did.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(&mut self, grid_width: u32, grid_height: u32, wrap_edges: bool,
                   is_position_clear: impl Fn(u32, u32) -> bool,
-                  food_at_position: impl Fn(u32, u32) -> bool,
-                  lifespan_multiplier: u32) {
+                  lifespan_multiplier: u32,
+                  move_energy_cost: u32,
+                  move_food_cost: f32,
+                  sighting: Option<(Direction, CellState)>,
+                  food_density: Option<f32>,
+                  food_direction: Option<Direction>,
+                  rng: &mut impl Rng) -> OrganismAction {
         if !self.is_alive {
-            return;
+            return OrganismAction::Idle;
         }
         
         self.lifetime += 1;
+        self.steps_since_reproduction = self.steps_since_reproduction.saturating_add(1);
         
         // Check if organism died of old age
         if self.lifetime >= self.max_lifespan(lifespan_multiplier) {
-            self.is_alive = false;
-            return;
+            self.die(DeathCause::OldAge);
+            return OrganismAction::DiedOfAge;
         }
         
-        // Try to eat food
+        // Stay and graze while food is plentiful nearby
+        if food_density.is_some_and(|density| rng.gen::<f32>() < density) {
+            return OrganismAction::Idle;
+        }
+        
+        // Try to move or rotate
+        if !self.has_movers() {
+            return OrganismAction::Idle;
+        }
+        
+        // Let the eyes pick the direction when they see something interesting
+        let decision = self.decide_move(sighting);
+        self.steer(decision);
+        
+        let from = (self.x, self.y);
+        if self.try_move(grid_width, grid_height, wrap_edges, &is_position_clear,
+                         move_energy_cost, move_food_cost, food_direction, rng) {
+            return OrganismAction::Moved { from, to: (self.x, self.y) };
+        }
+        
+        // If couldn't move, turn to face the target, or try a random rotation without one
+        let rotated = match decision {
+            Some(direction) => self.try_rotate_to(direction, grid_width, grid_height, wrap_edges,
+                                                  is_position_clear),
+            None => self.try_rotate(grid_width, grid_height, wrap_edges, is_position_clear, rng),
+        };
+        if rotated {
+            OrganismAction::Rotated { to: self.rotation }
+        } else {
+            OrganismAction::Blocked
+        }
+    }
+}
+
+/// Error returned when an imported organism or a default anatomy is malformed
+#[derive(Debug)]
+pub enum OrganismParseError {
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+    MissingCenter,
+    MultipleCenters,
+    DuplicateCell { x: i32, y: i32 },
+    EnvironmentCell { x: i32, y: i32 }, // Empty, food, wall or hazard in a body layout
+    UnknownCellState(String),
+    InvalidDirection(u8),
+}
+
+impl fmt::Display for OrganismParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            #[cfg(feature = "serde")]
+            OrganismParseError::Json(err) => write!(f, "invalid organism JSON: {}", err),
+            OrganismParseError::MissingCenter => write!(f, "organism has no cell at (0, 0)"),
+            OrganismParseError::MultipleCenters => write!(f, "organism has more than one cell at (0, 0)"),
+            OrganismParseError::DuplicateCell { x, y } => {
+                write!(f, "organism has more than one cell at ({}, {})", x, y)
+            }
+            OrganismParseError::EnvironmentCell { x, y } => {
+                write!(f, "organism has an environment cell at ({}, {})", x, y)
+            }
+            OrganismParseError::UnknownCellState(name) => {
+                write!(f, "unknown organism cell state \"{}\"", name)
+            }
+            OrganismParseError::InvalidDirection(dir) => write!(f, "invalid eye direction {}", dir),
+        }
+    }
+}
+
+impl std::error::Error for OrganismParseError {}
+
+/// Check that a cell layout has exactly one center cell, no overlapping cells
+/// and only body cells (no empty, food, wall or hazard)
+pub(crate) fn validate_layout(cells: &[OrganismCell]) -> Result<(), OrganismParseError> {
+    let centers = cells.iter().filter(|cell| cell.x == 0 && cell.y == 0).count();
+    match centers {
+        0 => return Err(OrganismParseError::MissingCenter),
+        1 => {}
+        _ => return Err(OrganismParseError::MultipleCenters),
+    }
+    
+    for (i, cell) in cells.iter().enumerate() {
+        if cells[..i].iter().any(|other| other.x == cell.x && other.y == cell.y) {
+            return Err(OrganismParseError::DuplicateCell { x: cell.x, y: cell.y });
+        }
+    }
+    
+    if let Some(cell) = cells.iter().find(|cell| cell.state.is_environment()) {
+        return Err(OrganismParseError::EnvironmentCell { x: cell.x, y: cell.y });
+    }
+    
+    Ok(())
+}
+
+/// The shareable part of an organism: its body layout and heritable traits
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct OrganismGenome {
+    cells: Vec<OrganismCell>,
+    mutability: u8,
+    move_range: u32,
+    #[serde(default)]
+    brain: Option<Brain>,
+}
+
+#[cfg(feature = "serde")]
+impl Organism {
+    /// Export this organism's genome (cell layout, mutability, move range and brain) as JSON
+    pub fn to_json(&self) -> String {
+        let genome = OrganismGenome {
+            cells: self.cells.clone(),
+            mutability: self.mutability,
+            move_range: self.move_range,
+            brain: self.brain,
+        };
+        serde_json::to_string(&genome).expect("organism genome is always serializable")
+    }
+    
+    /// Import an organism from JSON produced by `to_json`.
+    /// Runtime state (id, position, food, lifetime) starts fresh.
+    pub fn from_json(s: &str) -> Result<Organism, OrganismParseError> {
+        let genome: OrganismGenome = serde_json::from_str(s).map_err(OrganismParseError::Json)?;
+        validate_layout(&genome.cells)?;
+        
+        let mut organism = Organism::new(0, 0, 0);
+        organism.cells = genome.cells;
+        organism.health = organism.cells.len() as u32;
+        organism.energy = organism.max_energy();
+        organism.anatomy_changed();
+        organism.mutability = genome.mutability;
+        organism.move_range = genome.move_range;
+        organism.brain = genome.brain;
+        Ok(organism)
+    }
+    
+    /// Import an organism exported by the original JavaScript Life Engine.
+    /// Only the anatomy, mutability and move range are kept; runtime state starts fresh.
+    pub fn from_lifeengine_json(s: &str) -> Result<Organism, OrganismParseError> {
+        let export: LifeEngineOrganism = serde_json::from_str(s).map_err(OrganismParseError::Json)?;
+        
+        let mut cells = Vec::with_capacity(export.anatomy.cells.len());
+        for cell in export.anatomy.cells {
+            let state = match cell.state.name.as_str() {
+                "mouth" => CellState::Mouth,
+                "producer" => CellState::Producer,
+                "mover" => CellState::Mover,
+                "killer" => CellState::Killer,
+                "armor" => CellState::Armor,
+                "eye" => CellState::Eye,
+                _ => return Err(OrganismParseError::UnknownCellState(cell.state.name)),
+            };
+            let mut organism_cell = OrganismCell::new(state, cell.loc_col, cell.loc_row);
+            if state == CellState::Eye {
+                organism_cell.direction = Some(match cell.direction.unwrap_or(0) {
+                    0 => Direction::Up,
+                    1 => Direction::Right,
+                    2 => Direction::Down,
+                    3 => Direction::Left,
+                    dir => return Err(OrganismParseError::InvalidDirection(dir)),
+                });
+            }
+            cells.push(organism_cell);
+        }
+        validate_layout(&cells)?;
+        
+        let mut organism = Organism::new(0, 0, 0);
+        organism.cells = cells;
+        organism.health = organism.cells.len() as u32;
+        organism.energy = organism.max_energy();
+        organism.anatomy_changed();
+        if let Some(mutability) = export.mutability {
+            organism.mutability = mutability.round().clamp(1.0, 100.0) as u8;
+        }
+        if let Some(move_range) = export.move_range {
+            organism.move_range = move_range.max(1);
+        }
+        Ok(organism)
+    }
+}
+
+/// The parts of a Life Engine organism export we read; everything else is ignored
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct LifeEngineOrganism {
+    anatomy: LifeEngineAnatomy,
+    mutability: Option<f64>,  // Percent chance to mutate, may be fractional
+    move_range: Option<u32>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct LifeEngineAnatomy {
+    cells: Vec<LifeEngineCell>,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct LifeEngineCell {
+    loc_col: i32,              // Relative x
+    loc_row: i32,              // Relative y
+    state: LifeEngineState,
+    direction: Option<u8>,     // Eye facing, 0-3 clockwise from up like `Direction`
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct LifeEngineState {
+    name: String,
+}
+
+/// Error returned when a genome string is malformed
+#[derive(Debug)]
+pub enum GenomeParseError {
+    MissingField(&'static str),
+    InvalidNumber(String),
+    InvalidCell(String),
+    UnknownState(String),
+    UnknownDirection(String),
+    InvalidBrain(String),
+    Layout(OrganismParseError),
+}
+
+impl fmt::Display for GenomeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenomeParseError::MissingField(field) => write!(f, "genome is missing its {} field", field),
+            GenomeParseError::InvalidNumber(s) => write!(f, "invalid number '{}' in genome", s),
+            GenomeParseError::InvalidCell(s) => write!(f, "invalid cell '{}' in genome", s),
+            GenomeParseError::UnknownState(s) => write!(f, "unknown cell state '{}' in genome", s),
+            GenomeParseError::UnknownDirection(s) => write!(f, "unknown direction '{}' in genome", s),
+            GenomeParseError::InvalidBrain(s) => write!(f, "invalid brain '{}' in genome", s),
+            GenomeParseError::Layout(err) => write!(f, "invalid genome layout: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for GenomeParseError {}
+
+impl Organism {
+    /// Export this organism's genome as a compact string:
+    /// `mutability;move_range[;R=brain];x,y,S[,D];...` where S is a cell state letter
+    /// (M, P, V, K, A, E, H), D an optional direction letter (U, R, D, L) and the
+    /// brain, if any, is one reaction letter (I, C, F) per cell state.
+    pub fn to_genome(&self) -> String {
+        let mut genome = format!("{};{}", self.mutability, self.move_range);
+        if let Some(brain) = &self.brain {
+            genome.push_str(&format!(";R={}", brain.to_letters()));
+        }
         for cell in &self.cells {
-            if cell.state == CellStates::Mouth {
-                // Check adjacent positions for food
-                let (cx, cy) = self.get_cell_position(cell);
-                let adjacents = [(0, 1), (1, 0), (0, -1), (-1, 0)];
-                
-                for (dx, dy) in adjacents.iter() {
-                    let fx = (cx as i32 + dx).max(0).min(grid_width as i32 - 1) as u32;
-                    let fy = (cy as i32 + dy).max(0).min(grid_height as i32 - 1) as u32;
-                    
-                    if food_at_position(fx, fy) {
-                        self.food_collected += 1;
-                    }
-                }
+            genome.push_str(&format!(";{},{},{}", cell.x, cell.y, state_letter(cell.state)));
+            if let Some(dir) = cell.direction {
+                genome.push(',');
+                genome.push(direction_letter(dir));
             }
         }
+        genome
+    }
+    
+    /// Import an organism from a string produced by `to_genome`.
+    /// Runtime state (id, position, food, lifetime) starts fresh.
+    pub fn from_genome(s: &str) -> Result<Organism, GenomeParseError> {
+        let mut fields = s.trim().split(';');
+        let mutability = parse_genome_number(fields.next().filter(|f| !f.is_empty()), "mutability")?;
+        let move_range = parse_genome_number(fields.next(), "move range")?;
         
-        // Try to move or rotate
-        if self.has_movers() {
-            let moved = self.try_move(grid_width, grid_height, |x, y| is_position_clear(x, y));
+        let mut cells = Vec::new();
+        let mut brain = None;
+        for field in fields {
+            if let Some(letters) = field.trim().strip_prefix("R=") {
+                brain = Some(Brain::from_letters(letters)
+                    .ok_or_else(|| GenomeParseError::InvalidBrain(letters.to_string()))?);
+                continue;
+            }
+            let parts: Vec<&str> = field.split(',').collect();
+            if parts.len() != 3 && parts.len() != 4 {
+                return Err(GenomeParseError::InvalidCell(field.to_string()));
+            }
             
-            if !moved {
-                // If couldn't move, try to rotate
-                self.try_rotate(|x, y| is_position_clear(x, y));
+            let x = parse_genome_number(Some(parts[0]), "cell x")?;
+            let y = parse_genome_number(Some(parts[1]), "cell y")?;
+            let state = parse_state_letter(parts[2])
+                .ok_or_else(|| GenomeParseError::UnknownState(parts[2].to_string()))?;
+            let direction = match parts.get(3) {
+                Some(dir) => Some(parse_direction_letter(dir)
+                    .ok_or_else(|| GenomeParseError::UnknownDirection(dir.to_string()))?),
+                None => None,
+            };
+            
+            cells.push(OrganismCell { state, x, y, direction });
+        }
+        validate_layout(&cells).map_err(GenomeParseError::Layout)?;
+        
+        let mut organism = Organism::new(0, 0, 0);
+        organism.cells = cells;
+        organism.health = organism.cells.len() as u32;
+        organism.energy = organism.max_energy();
+        organism.anatomy_changed();
+        organism.mutability = mutability;
+        organism.move_range = move_range;
+        organism.brain = brain;
+        Ok(organism)
+    }
+    
+    /// Draw the body as rows of cell letters (M, P, V, K, A, E, H), with `.` for no cell.
+    /// The grid is the smallest one centered on (0, 0), so it always has a middle cell;
+    /// rows run from the top (most negative y) down. Eye directions and traits aren't shown.
+    ///
+    /// ```
+    /// use simulation::{CellState, Organism};
+    ///
+    /// let mut organism = Organism::new(0, 0, 0);
+    /// organism.add_cell(CellState::Producer, 1, 0);
+    /// organism.add_cell(CellState::Producer, 2, 0);
+    /// organism.add_cell(CellState::Killer, 0, -1);
+    /// organism.add_cell(CellState::Eye, -1, 1);
+    /// let ascii = organism.to_ascii();
+    /// assert_eq!(ascii, "..K..\n..MPP\n.E...");
+    ///
+    /// let copy = Organism::from_ascii(&ascii).unwrap();
+    /// assert_eq!(copy.species, organism.species);
+    /// assert_eq!(copy.to_ascii(), ascii);
+    /// ```
+    pub fn to_ascii(&self) -> String {
+        let (min_x, min_y, max_x, max_y) = self.bounds;
+        let reach_x = min_x.abs().max(max_x.abs());
+        let reach_y = min_y.abs().max(max_y.abs());
+        let rows: Vec<String> = (-reach_y..=reach_y).map(|y| {
+            (-reach_x..=reach_x).map(|x| {
+                self.cells.iter()
+                    .find(|cell| cell.x == x && cell.y == y)
+                    .map_or('.', |cell| state_letter(cell.state))
+            }).collect()
+        }).collect();
+        rows.join("\n")
+    }
+    
+    /// Import an organism drawn by `to_ascii`. The middle cell of the drawing is (0, 0)
+    /// and must be a mouth. Blank lines and spaces around rows are ignored.
+    /// Eyes face up; runtime state and traits start fresh.
+    ///
+    /// ```
+    /// use simulation::{AsciiParseError, Organism};
+    ///
+    /// assert!(matches!(Organism::from_ascii("\n"), Err(AsciiParseError::Empty)));
+    /// assert!(matches!(Organism::from_ascii("P.\nM."), Err(AsciiParseError::EvenSize { width: 2, height: 2 })));
+    /// assert!(matches!(Organism::from_ascii(".P.\nPM"), Err(AsciiParseError::RaggedRows)));
+    /// assert!(matches!(Organism::from_ascii("PPM"), Err(AsciiParseError::CenterNotMouth('P'))));
+    /// assert!(matches!(Organism::from_ascii("XMP"), Err(AsciiParseError::UnknownSymbol('X'))));
+    /// ```
+    pub fn from_ascii(s: &str) -> Result<Organism, AsciiParseError> {
+        let rows: Vec<Vec<char>> = s.lines()
+            .map(str::trim)
+            .filter(|row| !row.is_empty())
+            .map(|row| row.chars().collect())
+            .collect();
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if height == 0 {
+            return Err(AsciiParseError::Empty);
+        }
+        if rows.iter().any(|row| row.len() != width) {
+            return Err(AsciiParseError::RaggedRows);
+        }
+        if width.is_multiple_of(2) || height.is_multiple_of(2) {
+            return Err(AsciiParseError::EvenSize { width, height });
+        }
+        
+        let (center_x, center_y) = ((width / 2) as i32, (height / 2) as i32);
+        let center = rows[center_y as usize][center_x as usize];
+        if center != 'M' {
+            return Err(AsciiParseError::CenterNotMouth(center));
+        }
+        
+        let mut cells = Vec::new();
+        for (row, y) in rows.iter().zip(-center_y..) {
+            for (&symbol, x) in row.iter().zip(-center_x..) {
+                // Organism::new already places the center mouth
+                if symbol == '.' || (x == 0 && y == 0) {
+                    continue;
+                }
+                let state = parse_state_letter(&symbol.to_string())
+                    .ok_or(AsciiParseError::UnknownSymbol(symbol))?;
+                cells.push(OrganismCell::new(state, x, y));
+            }
+        }
+        let mut organism = Organism::new(0, 0, 0);
+        organism.cells.extend(cells);
+        organism.health = organism.cells.len() as u32;
+        organism.energy = organism.max_energy();
+        organism.anatomy_changed();
+        Ok(organism)
+    }
+}
+
+/// Error returned when an ASCII organism drawing is malformed
+#[derive(Debug)]
+pub enum AsciiParseError {
+    Empty,
+    RaggedRows,                                   // Rows of different lengths
+    EvenSize { width: usize, height: usize },     // No middle cell to put the center on
+    UnknownSymbol(char),
+    CenterNotMouth(char),
+}
+
+impl fmt::Display for AsciiParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsciiParseError::Empty => write!(f, "organism drawing is empty"),
+            AsciiParseError::RaggedRows => write!(f, "organism drawing has rows of different lengths"),
+            AsciiParseError::EvenSize { width, height } => {
+                write!(f, "organism drawing is {}x{}; both sides must be odd", width, height)
+            }
+            AsciiParseError::UnknownSymbol(c) => write!(f, "unknown symbol '{}' in organism drawing", c),
+            AsciiParseError::CenterNotMouth(c) => {
+                write!(f, "organism drawing has '{}' at its center instead of a mouth", c)
             }
         }
     }
 }
 
+impl std::error::Error for AsciiParseError {}
+
+/// Parse one numeric genome field, naming the field if it is missing
+fn parse_genome_number<T: std::str::FromStr>(field: Option<&str>, name: &'static str) -> Result<T, GenomeParseError> {
+    let field = field.ok_or(GenomeParseError::MissingField(name))?;
+    field.trim().parse().map_err(|_| GenomeParseError::InvalidNumber(field.to_string()))
+}
+
+/// Genome letter for an organism cell state
+fn state_letter(state: CellState) -> char {
+    match state {
+        CellState::Mouth => 'M',
+        CellState::Producer => 'P',
+        CellState::Mover => 'V',
+        CellState::Killer => 'K',
+        CellState::Armor => 'A',
+        CellState::Eye => 'E',
+        CellState::Heal => 'H',
+        // Environment states never appear in an organism body
+        CellState::Empty | CellState::Food | CellState::Wall | CellState::Hazard => '?',
+    }
+}
+
+fn parse_state_letter(s: &str) -> Option<CellState> {
+    match s.trim() {
+        "M" => Some(CellState::Mouth),
+        "P" => Some(CellState::Producer),
+        "V" => Some(CellState::Mover),
+        "K" => Some(CellState::Killer),
+        "A" => Some(CellState::Armor),
+        "E" => Some(CellState::Eye),
+        "H" => Some(CellState::Heal),
+        _ => None,
+    }
+}
+
+/// Genome letter for a cell direction
+fn direction_letter(dir: Direction) -> char {
+    match dir {
+        Direction::Up => 'U',
+        Direction::Right => 'R',
+        Direction::Down => 'D',
+        Direction::Left => 'L',
+    }
+}
+
+fn parse_direction_letter(s: &str) -> Option<Direction> {
+    match s.trim() {
+        "U" => Some(Direction::Up),
+        "R" => Some(Direction::Right),
+        "D" => Some(Direction::Down),
+        "L" => Some(Direction::Left),
+        _ => None,
+    }
+}
+
 /// Get a random cell state (excluding Empty, Food, and Wall which are environment states)
-fn random_cell_state() -> CellStates {
-    let state_idx = rand::thread_rng().gen_range(0..6);
+fn random_cell_state(rng: &mut impl Rng) -> CellState {
+    let state_idx = rng.gen_range(0..7);
     match state_idx {
-        0 => CellStates::Mouth,
-        1 => CellStates::Producer,
-        2 => CellStates::Mover,
-        3 => CellStates::Killer,
-        4 => CellStates::Armor,
-        5 => CellStates::Eye,
-        _ => CellStates::Mouth, // Won't happen due to range
-    }
-}
\ No newline at end of file
+        0 => CellState::Mouth,
+        1 => CellState::Producer,
+        2 => CellState::Mover,
+        3 => CellState::Killer,
+        4 => CellState::Armor,
+        5 => CellState::Eye,
+        6 => CellState::Heal,
+        _ => CellState::Mouth, // Won't happen due to range
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// A mouth with a mover beside it, loaded up with energy
+    fn mover_at(x: u32, y: u32) -> Organism {
+        let mut organism = Organism::new(1, x, y);
+        organism.add_cell(CellState::Mover, 1, 0);
+        organism.energy = 100;
+        organism
+    }
+
+    /// Every cell as (state, x, y, direction), for comparing layouts
+    fn layout(organism: &Organism) -> Vec<(CellState, i32, i32, Option<Direction>)> {
+        organism.cells.iter().map(|cell| (cell.state, cell.x, cell.y, cell.direction)).collect()
+    }
+
+    /// A small organism with every kind of genome detail set, plus some runtime state
+    fn designed_organism() -> Organism {
+        let mut organism = Organism::new(4, 12, 7);
+        organism.add_cell(CellState::Mover, 1, 0);
+        organism.add_cell(CellState::Eye, 0, -1);
+        organism.cells[2].direction = Some(Direction::Left);
+        organism.add_cell(CellState::Killer, -1, 1);
+        organism.mutability = 7;
+        organism.move_range = 3;
+        organism.food_collected = 5;
+        organism.lifetime = 9;
+        organism
+    }
+
+    #[test]
+    fn genome_string_round_trip() {
+        let organism = designed_organism();
+        let imported = Organism::from_genome(&organism.to_genome()).unwrap();
+        assert_eq!(layout(&imported), layout(&organism));
+        assert_eq!((imported.mutability, imported.move_range), (7, 3));
+        assert_eq!((imported.id, imported.food_collected, imported.lifetime), (0, 0, 0));
+        assert_eq!(imported.species, organism.species);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let organism = designed_organism();
+        let imported = Organism::from_json(&organism.to_json()).unwrap();
+        assert_eq!(layout(&imported), layout(&organism));
+        assert_eq!((imported.mutability, imported.move_range), (7, 3));
+        assert_eq!((imported.id, imported.food_collected, imported.lifetime), (0, 0, 0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_without_one_center_or_with_overlaps_is_rejected() {
+        let mut headless = designed_organism();
+        headless.cells.retain(|cell| (cell.x, cell.y) != (0, 0));
+        assert!(matches!(Organism::from_json(&headless.to_json()), Err(OrganismParseError::MissingCenter)));
+
+        let mut overlapping = designed_organism();
+        overlapping.cells.push(OrganismCell::new(CellState::Armor, 1, 0));
+        assert!(matches!(Organism::from_json(&overlapping.to_json()),
+                         Err(OrganismParseError::DuplicateCell { x: 1, y: 0 })));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_with_an_environment_cell_is_rejected() {
+        let json = r#"{"cells":[{"state":"Mouth","x":0,"y":0,"direction":null},
+                              {"state":"Wall","x":1,"y":0,"direction":null}],
+                      "mutability":5,"move_range":4}"#;
+        assert!(matches!(Organism::from_json(json), Err(OrganismParseError::EnvironmentCell { x: 1, y: 0 })));
+    }
+
+    #[test]
+    fn zero_mutation_chances_give_identical_offspring() {
+        let mut parent = designed_organism();
+        parent.mutability = 100;
+        let never = MutationParams { add_prob: 0.0, change_prob: 0.0, remove_prob: 0.0,
+                                     move_range_mutate_prob: 0.0, mutability_mutate_prob: 0.0,
+                                     brain_mutate_prob: 0.0 };
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..50 {
+            let child = Organism::new_from_parent(9, 0, 0, &parent, &never, 0, &mut rng);
+            assert_eq!(layout(&child), layout(&parent));
+            assert_eq!((child.mutability, child.move_range), (parent.mutability, parent.move_range));
+        }
+        // The default chances do change a fully mutable parent's children
+        let changed = (0..50).any(|_| {
+            let child = Organism::new_from_parent(9, 0, 0, &parent, &MutationParams::default(), 0, &mut rng);
+            layout(&child) != layout(&parent)
+        });
+        assert!(changed);
+    }
+
+    /// Whether every cell is reachable from the center through side-by-side cells
+    fn is_connected(organism: &Organism) -> bool {
+        let positions: Vec<(i32, i32)> = organism.cells.iter().map(|cell| (cell.x, cell.y)).collect();
+        let mut reached = vec![(0, 0)];
+        let mut frontier = vec![(0, 0)];
+        while let Some((x, y)) = frontier.pop() {
+            for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if positions.contains(&next) && !reached.contains(&next) {
+                    reached.push(next);
+                    frontier.push(next);
+                }
+            }
+        }
+        reached.len() == positions.len()
+    }
+
+    #[test]
+    fn repeated_mutation_keeps_the_body_whole() {
+        let mut rng = ChaCha8Rng::seed_from_u64(11);
+        let mut organism = Organism::new(1, 50, 50);
+        for _ in 0..3000 {
+            organism.mutate(&MutationParams::default(), 0, &mut rng);
+            let mut positions: Vec<(i32, i32)> = organism.cells.iter().map(|cell| (cell.x, cell.y)).collect();
+            positions.sort();
+            positions.dedup();
+            assert_eq!(positions.len(), organism.cells.len(), "overlapping cells");
+            assert!(is_connected(&organism));
+            assert_eq!(organism.health, organism.cells.len() as u32);
+        }
+    }
+
+    #[test]
+    fn mutation_spares_the_center_wherever_it_is_stored() {
+        // Imported bodies keep their order, so the center needn't come first
+        let mut organism = Organism::from_genome("100;4;1,0,P;0,0,M;-1,0,P").unwrap();
+        let only_changes = MutationParams { add_prob: 0.0, change_prob: 100.0, remove_prob: 0.0,
+                                            move_range_mutate_prob: 0.0, mutability_mutate_prob: 0.0,
+                                            brain_mutate_prob: 0.0 };
+        let mut rng = ChaCha8Rng::seed_from_u64(3);
+        let mut first_changed = false;
+        for _ in 0..50 {
+            organism.mutate(&only_changes, 0, &mut rng);
+            assert_eq!(organism.cells[1].state, CellState::Mouth);
+            first_changed |= organism.cells[0].state != CellState::Producer;
+        }
+        assert!(first_changed);
+    }
+
+    #[test]
+    fn mostly_adding_mutations_grow_bodies_over_generations() {
+        let growing = MutationParams { add_prob: 80.0, change_prob: 10.0, remove_prob: 10.0,
+                                       ..MutationParams::default() };
+        let mut rng = ChaCha8Rng::seed_from_u64(5);
+        let mut bodies: Vec<Organism> = (0..100).map(|id| Organism::new(id, 50, 50)).collect();
+        let mut mean_sizes = Vec::new();
+        for generation in 1..=300 {
+            for body in &mut bodies {
+                body.mutate(&growing, 0, &mut rng);
+            }
+            if [1, 10, 100, 300].contains(&generation) {
+                let total: usize = bodies.iter().map(|body| body.cells.len()).sum();
+                mean_sizes.push(total as f32 / bodies.len() as f32);
+            }
+        }
+        assert!(mean_sizes.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", mean_sizes);
+        // Not just a few outliers: by the end no body is still a lone cell
+        assert!(bodies.iter().all(|body| body.cells.len() > 1));
+    }
+
+    #[test]
+    fn only_adding_grows_every_generation_up_to_the_cap() {
+        let only_adds = MutationParams { add_prob: 100.0, change_prob: 0.0, remove_prob: 0.0,
+                                         mutability_mutate_prob: 0.0, ..MutationParams::default() };
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        for max_cells in [0, 8] {
+            let mut parent = Organism::new(1, 50, 50);
+            parent.mutability = 100; // Every child mutates
+            for _ in 0..40 {
+                let child = Organism::new_from_parent(2, 50, 50, &parent, &only_adds, max_cells, &mut rng);
+                assert!(child.cells.len() >= parent.cells.len());
+                if max_cells > 0 {
+                    assert!(child.cells.len() <= max_cells);
+                }
+                parent = child;
+            }
+            match max_cells {
+                0 => assert!(parent.cells.len() > 8),
+                cap => assert_eq!(parent.cells.len(), cap),
+            }
+        }
+    }
+
+    #[test]
+    fn species_follows_the_body_layout() {
+        // Same cells added in a different order, at another spot and facing another way
+        let mut a = Organism::new(1, 10, 10);
+        a.add_cell(CellState::Producer, 1, 0);
+        a.add_cell(CellState::Mover, 0, 1);
+        let mut b = Organism::new(2, 30, 40);
+        b.add_cell(CellState::Mover, 0, 1);
+        b.add_cell(CellState::Producer, 1, 0);
+        b.rotation = Direction::Left;
+        assert_eq!(a.species, b.species);
+
+        let mut rng = ChaCha8Rng::seed_from_u64(4);
+        let only_changes = MutationParams { change_prob: 100.0, add_prob: 0.0, remove_prob: 0.0,
+                                            ..MutationParams::default() };
+        a.mutability = 0;
+        let clone = Organism::new_from_parent(3, 20, 20, &a, &only_changes, 0, &mut rng);
+        assert_eq!(clone.species, a.species);
+        a.mutability = 100;
+        let mutant = Organism::new_from_parent(4, 20, 20, &a, &only_changes, 0, &mut rng);
+        assert_ne!(layout(&mutant), layout(&a));
+        assert_ne!(mutant.species, a.species);
+    }
+
+    #[test]
+    fn size_cap_stops_growth_at_five_cells_and_zero_means_unlimited() {
+        let mostly_adds = MutationParams { add_prob: 90.0, change_prob: 10.0, remove_prob: 5.0,
+                                           ..MutationParams::default() };
+        let grown = |max_cells: usize| {
+            let mut rng = ChaCha8Rng::seed_from_u64(6);
+            let mut organism = Organism::new(1, 50, 50);
+            for _ in 0..500 {
+                organism.mutate(&mostly_adds, max_cells, &mut rng);
+                if max_cells > 0 {
+                    assert!(organism.cells.len() <= max_cells);
+                }
+            }
+            organism.cells.len()
+        };
+        assert_eq!(grown(5), 5);
+        assert!(grown(0) > 100, "{}", grown(0));
+    }
+
+    #[test]
+    fn six_cell_mover_needs_its_cells_plus_surcharge_times_the_multiplier() {
+        let mut organism = mover_at(10, 10);
+        organism.add_cell(CellState::Producer, -1, 0);
+        organism.add_cell(CellState::Armor, 0, 1);
+        organism.add_cell(CellState::Armor, 0, -1);
+        organism.add_cell(CellState::Killer, 2, 0);
+        assert_eq!(organism.cells.len(), 6);
+        let mut rng = ChaCha8Rng::seed_from_u64(2);
+        let params = MutationParams::default();
+
+        for multiplier in [1.0f32, 1.5, 2.0, 0.3] {
+            let needed = ((6 + 1) as f32 * multiplier).ceil() as u32;
+            assert_eq!(organism.food_needed_to_reproduce(1, multiplier), needed);
+            organism.food_collected = needed - 1;
+            assert!(organism.try_reproduce(&params, 0, 1, multiplier, false, &mut rng).is_none());
+            organism.food_collected = needed;
+            assert!(organism.try_reproduce(&params, 0, 1, multiplier, false, &mut rng).is_some());
+        }
+        assert_eq!(organism.food_needed_to_reproduce(1, 1.5), 11);
+
+        // Without the mover the same body is cheaper
+        organism.cells.retain(|cell| cell.state != CellState::Mover);
+        organism.add_cell(CellState::Producer, 1, 0);
+        assert_eq!(organism.food_needed_to_reproduce(1, 1.5), 9);
+    }
+
+    #[test]
+    fn adding_cells_leaves_energy_alone() {
+        let mut organism = Organism::new(1, 5, 5);
+        organism.energy = 3;
+        organism.add_cell(CellState::Producer, 1, 0);
+        assert_eq!(organism.energy, 3);
+    }
+
+    #[test]
+    fn move_against_the_wall_is_blocked_and_free() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut organism = mover_at(5, 0);
+        organism.move_direction = Direction::Up;
+        let moved = organism.try_move(10, 10, false, |_, _| true, 3, 0.0, None, &mut rng);
+        assert!(!moved);
+        assert_eq!((organism.x, organism.y), (5, 0));
+        assert_eq!(organism.energy, 100);
+    }
+
+    #[test]
+    fn move_taking_a_cell_off_the_grid_is_refused() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        // The center can step right, but the mover beside it would leave the grid
+        let mut organism = mover_at(8, 5);
+        organism.move_direction = Direction::Right;
+        let moved = organism.try_move(10, 10, false, |_, _| true, 3, 0.0, None, &mut rng);
+        assert!(!moved);
+        assert_eq!((organism.x, organism.y), (8, 5));
+        assert_eq!(organism.energy, 100);
+    }
+}
@@ -0,0 +1,90 @@
+// simulation/src/palette.rs
+
+use crate::CellState;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Every cell state, in the order palettes list their colors
+const STATES: [CellState; 11] = [
+    CellState::Empty,
+    CellState::Food,
+    CellState::Wall,
+    CellState::Mouth,
+    CellState::Producer,
+    CellState::Mover,
+    CellState::Killer,
+    CellState::Armor,
+    CellState::Eye,
+    CellState::Heal,
+    CellState::Hazard,
+];
+
+/// Color (0xRRGGBB) for each cell state, e.g. for colorblind-friendly or light themes.
+/// The default palette uses `CellState::to_color`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Palette {
+    colors: [u32; 11], // Indexed by cell state, in declaration order
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette { colors: STATES.map(|state| state.to_color()) }
+    }
+}
+
+impl Palette {
+    /// Build a palette from colors for Empty, Food, Wall, Mouth, Producer, Mover, Killer, Armor, Eye, Heal and Hazard
+    pub fn new(colors: [u32; 11]) -> Self {
+        Palette { colors }
+    }
+    
+    pub fn color(&self, state: CellState) -> u32 {
+        self.colors[state as usize]
+    }
+    
+    pub fn set_color(&mut self, state: CellState, color: u32) {
+        self.colors[state as usize] = color;
+    }
+
+    /// Every color, in the order `new` takes them
+    pub(crate) fn colors(&self) -> [u32; 11] {
+        self.colors
+    }
+}
+
+/// What the grid's pixels show. Anything but `Normal` repaints an overlay at the end of every step.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RenderMode {
+    #[default]
+    Normal,      // Palette colors by cell state
+    FoodHeatmap, // Empty and food cells shaded by how much food is nearby
+    OwnerMap,    // Organism cells in a color picked from their organism's ID
+    AgeMap,      // Organism cells from green (newborn) to red (about to die of old age)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn new_food_color_recolors_food_already_on_the_grid() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        grid.set_cell(2, 3, CellState::Food, None);
+        grid.set_cell(7, 7, CellState::Wall, None);
+        let wall_color = grid.get_pixel(7, 7);
+
+        let mut palette = grid.palette;
+        palette.set_color(CellState::Food, 0x123456);
+        grid.set_palette(palette);
+        assert_eq!(grid.get_pixel(2, 3), 0x123456);
+        assert_eq!(grid.get_pixel(7, 7), wall_color);
+        assert_eq!(grid.get_pixel(0, 0), palette.color(CellState::Empty));
+
+        // Food added later uses the new color too
+        grid.set_cell(4, 4, CellState::Food, None);
+        assert_eq!(grid.get_pixel(4, 4), 0x123456);
+    }
+}
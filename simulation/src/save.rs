@@ -0,0 +1,480 @@
+// simulation/src/save.rs
+
+use std::io::{self, Read, Write};
+use rand_chacha::ChaCha8Rng;
+use rand::SeedableRng;
+use crate::{Brain, Cell, CellState, DeathCause, DeathStats, Direction, Grid, MutationParams, Organism, OrganismCell, Palette, RenderMode};
+
+/// Magic bytes at the start of every saved grid
+const MAGIC: &[u8; 4] = b"LEGR";
+/// Bump whenever the layout below changes; older or newer files are rejected
+const FORMAT_VERSION: u32 = 1;
+
+/// Marker written in place of a missing owner, direction, death cause or food lifespan
+const NONE_U64: u64 = u64::MAX;
+const NONE_U32: u32 = u32::MAX;
+const NONE_U8: u8 = u8::MAX;
+
+impl Grid {
+    /// Write a binary checkpoint of the grid: cells, organisms, tunable parameters, palette,
+    /// render mode and RNG state. Statistics are not saved and start over after `load`, and
+    /// the species, owner and eye-direction display toggles come back switched off.
+    pub fn save(&self, w: impl Write) -> io::Result<()> {
+        let mut w = io::BufWriter::new(w);
+        w.write_all(MAGIC)?;
+        write_u32(&mut w, FORMAT_VERSION)?;
+
+        // Dimensions and tunable parameters
+        write_u32(&mut w, self.width)?;
+        write_u32(&mut w, self.height)?;
+        write_f32(&mut w, self.food_production_prob)?;
+        write_f32(&mut w, self.food_drop_prob)?;
+        write_u64(&mut w, self.next_organism_id as u64)?;
+        write_u64(&mut w, self.max_organisms as u64)?;
+        write_u32(&mut w, self.lifespan_multiplier)?;
+        write_bool(&mut w, self.insta_kill)?;
+        write_u32(&mut w, self.heal_interval)?;
+        write_u32(&mut w, self.killer_damage)?;
+        write_u32(&mut w, self.armor_reduction)?;
+        write_bool(&mut w, self.armor_blocks_adjacent)?;
+        write_bool(&mut w, self.use_diagonal_neighbors)?;
+        write_bool(&mut w, self.food_blocks_reproduction)?;
+        write_bool(&mut w, self.movers_can_produce)?;
+        write_u32(&mut w, self.eye_view_distance)?;
+        write_u32(&mut w, self.sense_radius)?;
+        write_bool(&mut w, self.food_gradient_movement)?;
+        write_bool(&mut w, self.brains_enabled)?;
+        write_bool(&mut w, self.wrap_edges)?;
+        write_u32(&mut w, self.producer_energy_cost)?;
+        write_u32(&mut w, self.move_energy_cost)?;
+        write_f32(&mut w, self.move_food_cost)?;
+        write_u32(&mut w, self.starvation_steps)?;
+        write_u32(&mut w, self.energy_regen)?;
+        write_bool(&mut w, self.metabolism_enabled)?;
+        write_u32(&mut w, self.cell_upkeep)?;
+        write_u32(&mut w, self.food_energy)?;
+        let mutation = &self.mutation_params;
+        for prob in [mutation.add_prob, mutation.change_prob, mutation.remove_prob,
+                     mutation.move_range_mutate_prob, mutation.mutability_mutate_prob,
+                     mutation.brain_mutate_prob] {
+            write_f32(&mut w, prob)?;
+        }
+        write_u64(&mut w, self.max_organism_cells as u64)?;
+        write_u32(&mut w, self.extra_mover_food_cost)?;
+        write_f32(&mut w, self.reproduction_cost_multiplier)?;
+        write_f32(&mut w, self.soft_cap_fraction)?;
+        write_bool(&mut w, self.deterministic_placement)?;
+        write_u32(&mut w, self.reproduction_cooldown)?;
+        let anatomy = self.default_anatomy().unwrap_or(&[]);
+        write_u32(&mut w, anatomy.len() as u32)?;
+        for &(state, x, y) in anatomy {
+            w.write_all(&x.to_le_bytes())?;
+            w.write_all(&y.to_le_bytes())?;
+            w.write_all(&[state as u8])?;
+        }
+        write_u64(&mut w, self.stats_history_len as u64)?;
+        write_u32(&mut w, self.food_lifespan.unwrap_or(NONE_U32))?;
+        write_bool(&mut w, self.sparse_food_scan)?;
+        write_u64(&mut w, self.tick_count)?;
+        let deaths = &self.death_stats;
+        for count in [deaths.old_age, deaths.killed, deaths.starved] {
+            write_u64(&mut w, count)?;
+        }
+        for color in self.palette.colors() {
+            write_u32(&mut w, color)?;
+        }
+        w.write_all(&[self.render_mode as u8])?;
+
+        // RNG position, so a loaded grid continues exactly where this one left off
+        w.write_all(&self.rng.get_seed())?;
+        write_u64(&mut w, self.rng.get_stream())?;
+        w.write_all(&self.rng.get_word_pos().to_le_bytes())?;
+
+        for (cell, &light) in self.cells.iter().zip(&self.light) {
+            w.write_all(&[cell.state as u8])?;
+            write_u64(&mut w, cell.owner.map_or(NONE_U64, |owner| owner as u64))?;
+            write_u32(&mut w, cell.food_age)?;
+            write_f32(&mut w, light)?;
+        }
+
+        write_u64(&mut w, self.organisms.len() as u64)?;
+        for org in &self.organisms {
+            write_organism(&mut w, org)?;
+        }
+
+        w.flush()
+    }
+
+    /// Read a grid written by `save`. Files from another format version are rejected.
+    pub fn load(r: impl Read) -> io::Result<Grid> {
+        let mut r = io::BufReader::new(r);
+        let mut magic = [0u8; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a saved grid".to_string()));
+        }
+        let version = read_u32(&mut r)?;
+        if version != FORMAT_VERSION {
+            return Err(invalid_data(format!(
+                "unsupported grid format version {} (expected {})", version, FORMAT_VERSION)));
+        }
+
+        let width = read_u32(&mut r)?;
+        let height = read_u32(&mut r)?;
+        let mut grid = Grid::new_seeded(width, height, 0)
+            .map_err(|err| invalid_data(err.to_string()))?;
+        grid.food_production_prob = read_f32(&mut r)?;
+        grid.food_drop_prob = read_f32(&mut r)?;
+        grid.next_organism_id = read_u64(&mut r)? as usize;
+        grid.max_organisms = read_u64(&mut r)? as usize;
+        grid.lifespan_multiplier = read_u32(&mut r)?;
+        grid.insta_kill = read_bool(&mut r)?;
+        grid.heal_interval = read_u32(&mut r)?;
+        grid.killer_damage = read_u32(&mut r)?;
+        grid.armor_reduction = read_u32(&mut r)?;
+        grid.armor_blocks_adjacent = read_bool(&mut r)?;
+        grid.use_diagonal_neighbors = read_bool(&mut r)?;
+        grid.food_blocks_reproduction = read_bool(&mut r)?;
+        grid.movers_can_produce = read_bool(&mut r)?;
+        grid.eye_view_distance = read_u32(&mut r)?;
+        grid.sense_radius = read_u32(&mut r)?;
+        grid.food_gradient_movement = read_bool(&mut r)?;
+        grid.brains_enabled = read_bool(&mut r)?;
+        grid.wrap_edges = read_bool(&mut r)?;
+        grid.producer_energy_cost = read_u32(&mut r)?;
+        grid.move_energy_cost = read_u32(&mut r)?;
+        grid.move_food_cost = read_f32(&mut r)?;
+        grid.starvation_steps = read_u32(&mut r)?;
+        grid.energy_regen = read_u32(&mut r)?;
+        grid.metabolism_enabled = read_bool(&mut r)?;
+        grid.cell_upkeep = read_u32(&mut r)?;
+        grid.food_energy = read_u32(&mut r)?;
+        grid.mutation_params = MutationParams {
+            add_prob: read_f32(&mut r)?,
+            change_prob: read_f32(&mut r)?,
+            remove_prob: read_f32(&mut r)?,
+            move_range_mutate_prob: read_f32(&mut r)?,
+            mutability_mutate_prob: read_f32(&mut r)?,
+            brain_mutate_prob: read_f32(&mut r)?,
+        };
+        grid.max_organism_cells = read_u64(&mut r)? as usize;
+        grid.extra_mover_food_cost = read_u32(&mut r)?;
+        grid.reproduction_cost_multiplier = read_f32(&mut r)?;
+        grid.soft_cap_fraction = read_f32(&mut r)?;
+        grid.deterministic_placement = read_bool(&mut r)?;
+        grid.reproduction_cooldown = read_u32(&mut r)?;
+        let anatomy_len = read_u32(&mut r)?;
+        // Lengths come from the file, so grow as entries are read rather than trusting them up front
+        let mut anatomy = Vec::new();
+        for _ in 0..anatomy_len {
+            let x = read_i32(&mut r)?;
+            let y = read_i32(&mut r)?;
+            anatomy.push((read_cell_state(&mut r)?, x, y));
+        }
+        grid.set_default_anatomy(anatomy).map_err(|err| invalid_data(err.to_string()))?;
+        grid.stats_history_len = read_u64(&mut r)? as usize;
+        grid.food_lifespan = match read_u32(&mut r)? {
+            NONE_U32 => None,
+            lifespan => Some(lifespan),
+        };
+        grid.sparse_food_scan = read_bool(&mut r)?;
+        grid.tick_count = read_u64(&mut r)?;
+        grid.death_stats = DeathStats {
+            old_age: read_u64(&mut r)?,
+            killed: read_u64(&mut r)?,
+            starved: read_u64(&mut r)?,
+        };
+        let mut colors = [0u32; 11];
+        for color in &mut colors {
+            *color = read_u32(&mut r)?;
+        }
+        grid.palette = Palette::new(colors);
+        grid.render_mode = match read_u8(&mut r)? {
+            0 => RenderMode::Normal,
+            1 => RenderMode::FoodHeatmap,
+            2 => RenderMode::OwnerMap,
+            3 => RenderMode::AgeMap,
+            value => return Err(invalid_data(format!("invalid render mode {}", value))),
+        };
+
+        let mut seed = [0u8; 32];
+        r.read_exact(&mut seed)?;
+        let stream = read_u64(&mut r)?;
+        let mut word_pos = [0u8; 16];
+        r.read_exact(&mut word_pos)?;
+        grid.rng = ChaCha8Rng::from_seed(seed);
+        grid.rng.set_stream(stream);
+        grid.rng.set_word_pos(u128::from_le_bytes(word_pos));
+
+        for idx in 0..grid.cells.len() {
+            let state = read_cell_state(&mut r)?;
+            let owner = read_u64(&mut r)?;
+            let food_age = read_u32(&mut r)?;
+            grid.cells[idx] = Cell {
+                state,
+                owner: if owner == NONE_U64 { None } else { Some(owner as usize) },
+                food_age,
+            };
+            grid.light[idx] = read_f32(&mut r)?;
+        }
+
+        grid.rebuild_cell_sets();
+        
+        let organism_count = read_u64(&mut r)?;
+        for _ in 0..organism_count {
+            grid.organisms.push(read_organism(&mut r)?);
+        }
+        grid.refresh_pixels();
+
+        Ok(grid)
+    }
+}
+
+fn write_organism(w: &mut impl Write, org: &Organism) -> io::Result<()> {
+    write_u64(w, org.id as u64)?;
+    write_u32(w, org.x)?;
+    write_u32(w, org.y)?;
+    w.write_all(&[org.rotation as u8, org.move_direction as u8])?;
+    write_u32(w, org.food_collected)?;
+    write_u32(w, org.health)?;
+    write_u32(w, org.lifetime)?;
+    w.write_all(&[org.mutability])?;
+    write_u32(w, org.move_range)?;
+    write_u32(w, org.move_counter)?;
+    write_bool(w, org.is_alive)?;
+    write_u32(w, org.energy)?;
+    w.write_all(&[org.death_cause.map_or(NONE_U8, |cause| cause as u8)])?;
+    write_u64(w, org.parent_id.map_or(NONE_U64, |id| id as u64))?;
+    write_u32(w, org.kills)?;
+    write_u32(w, org.total_food_eaten)?;
+    write_f32(w, org.move_food_debt)?;
+    write_u32(w, org.hunger)?;
+    write_u32(w, org.max_lifetime)?;
+    write_u32(w, org.steps_since_reproduction)?;
+    match &org.brain {
+        Some(brain) => {
+            write_bool(w, true)?;
+            w.write_all(brain.to_letters().as_bytes())?;
+        }
+        None => write_bool(w, false)?,
+    }
+
+    write_u32(w, org.cells.len() as u32)?;
+    for cell in &org.cells {
+        w.write_all(&cell.x.to_le_bytes())?;
+        w.write_all(&cell.y.to_le_bytes())?;
+        w.write_all(&[cell.state as u8, cell.direction.map_or(NONE_U8, |dir| dir as u8)])?;
+    }
+    Ok(())
+}
+
+fn read_organism(r: &mut impl Read) -> io::Result<Organism> {
+    let mut org = Organism::new(0, 0, 0);
+    org.id = read_u64(r)? as usize;
+    org.x = read_u32(r)?;
+    org.y = read_u32(r)?;
+    org.rotation = read_direction(r)?;
+    org.move_direction = read_direction(r)?;
+    org.food_collected = read_u32(r)?;
+    org.health = read_u32(r)?;
+    org.lifetime = read_u32(r)?;
+    org.mutability = read_u8(r)?;
+    org.move_range = read_u32(r)?;
+    org.move_counter = read_u32(r)?;
+    org.is_alive = read_bool(r)?;
+    org.energy = read_u32(r)?;
+    org.death_cause = match read_u8(r)? {
+        NONE_U8 => None,
+        0 => Some(DeathCause::OldAge),
+        1 => Some(DeathCause::Killed),
+        2 => Some(DeathCause::Starved),
+        value => return Err(invalid_data(format!("invalid death cause {}", value))),
+    };
+    org.parent_id = match read_u64(r)? {
+        NONE_U64 => None,
+        id => Some(id as usize),
+    };
+    org.kills = read_u32(r)?;
+    org.total_food_eaten = read_u32(r)?;
+    org.move_food_debt = read_f32(r)?;
+    org.hunger = read_u32(r)?;
+    org.max_lifetime = read_u32(r)?;
+    org.steps_since_reproduction = read_u32(r)?;
+    if read_bool(r)? {
+        let mut letters = [0u8; 11];
+        r.read_exact(&mut letters)?;
+        org.brain = Some(std::str::from_utf8(&letters).ok()
+            .and_then(Brain::from_letters)
+            .ok_or_else(|| invalid_data("invalid organism brain".to_string()))?);
+    }
+
+    let cell_count = read_u32(r)?;
+    org.cells = Vec::new();
+    for _ in 0..cell_count {
+        let x = read_i32(r)?;
+        let y = read_i32(r)?;
+        let state = read_cell_state(r)?;
+        let direction = match read_u8(r)? {
+            NONE_U8 => None,
+            dir => Some(direction_from_u8(dir)?),
+        };
+        org.cells.push(OrganismCell { state, x, y, direction });
+    }
+    org.anatomy_changed();
+    Ok(org)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_cell_state(r: &mut impl Read) -> io::Result<CellState> {
+    let value = read_u8(r)?;
+    let state = match value {
+        0 => CellState::Empty,
+        1 => CellState::Food,
+        2 => CellState::Wall,
+        3 => CellState::Mouth,
+        4 => CellState::Producer,
+        5 => CellState::Mover,
+        6 => CellState::Killer,
+        7 => CellState::Armor,
+        8 => CellState::Eye,
+        9 => CellState::Heal,
+        10 => CellState::Hazard,
+        _ => return Err(invalid_data(format!("invalid cell state {}", value))),
+    };
+    Ok(state)
+}
+
+fn direction_from_u8(value: u8) -> io::Result<Direction> {
+    match value {
+        0 => Ok(Direction::Up),
+        1 => Ok(Direction::Right),
+        2 => Ok(Direction::Down),
+        3 => Ok(Direction::Left),
+        _ => Err(invalid_data(format!("invalid direction {}", value))),
+    }
+}
+
+fn read_direction(r: &mut impl Read) -> io::Result<Direction> {
+    direction_from_u8(read_u8(r)?)
+}
+
+fn write_u32(w: &mut impl Write, value: u32) -> io::Result<()> {
+    w.write_all(&value.to_le_bytes())
+}
+
+fn write_u64(w: &mut impl Write, value: u64) -> io::Result<()> {
+    w.write_all(&value.to_le_bytes())
+}
+
+fn write_f32(w: &mut impl Write, value: f32) -> io::Result<()> {
+    w.write_all(&value.to_le_bytes())
+}
+
+fn write_bool(w: &mut impl Write, value: bool) -> io::Result<()> {
+    w.write_all(&[value as u8])
+}
+
+fn read_u8(r: &mut impl Read) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_u32(r: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_i32(r: &mut impl Read) -> io::Result<i32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(i32::from_le_bytes(buf))
+}
+
+fn read_u64(r: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+fn read_f32(r: &mut impl Read) -> io::Result<f32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(f32::from_le_bytes(buf))
+}
+
+fn read_bool(r: &mut impl Read) -> io::Result<bool> {
+    Ok(read_u8(r)? != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loaded_grid_carries_on_like_the_original() {
+        let mut grid = Grid::new_seeded(40, 40, 3).unwrap();
+        grid.food_production_prob = 10.0;
+        grid.origin_of_life();
+        grid.step_n(100);
+
+        let mut bytes = Vec::new();
+        grid.save(&mut bytes).unwrap();
+        let mut loaded = Grid::load(bytes.as_slice()).unwrap();
+        assert_eq!(loaded.tick_count, grid.tick_count);
+        assert_eq!(loaded.pixels, grid.pixels);
+        let cells = |grid: &Grid| grid.cells.iter().map(|cell| (cell.state, cell.owner)).collect::<Vec<_>>();
+        assert_eq!(cells(&loaded), cells(&grid));
+
+        // Saving includes the RNG, so both go on to the same future
+        grid.step_n(50);
+        loaded.step_n(50);
+        assert_eq!(loaded.pixels, grid.pixels);
+        let positions = |grid: &Grid| grid.organisms.iter().map(|org| (org.id, org.x, org.y, org.energy)).collect::<Vec<_>>();
+        assert_eq!(positions(&loaded), positions(&grid));
+    }
+
+    #[test]
+    fn other_format_versions_are_rejected() {
+        let mut bytes = Vec::new();
+        Grid::new_seeded(4, 4, 1).unwrap().save(&mut bytes).unwrap();
+        bytes[4..8].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        let err = Grid::load(bytes.as_slice()).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn palette_and_render_mode_are_saved() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        grid.create_basic_organism(5, 5);
+        let mut palette = Palette::default();
+        palette.set_color(CellState::Producer, 0x123456);
+        grid.set_palette(palette);
+        grid.set_render_mode(RenderMode::OwnerMap);
+
+        let mut bytes = Vec::new();
+        grid.save(&mut bytes).unwrap();
+        let loaded = Grid::load(bytes.as_slice()).unwrap();
+        assert_eq!(loaded.palette, palette);
+        assert_eq!(loaded.render_mode, RenderMode::OwnerMap);
+        assert_eq!(loaded.pixels, grid.pixels);
+    }
+
+    #[test]
+    fn huge_cell_count_is_an_error_not_an_abort() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        grid.create_basic_organism(5, 5);
+        let mut bytes = Vec::new();
+        grid.save(&mut bytes).unwrap();
+
+        // The organism's cell count sits just before its three 10-byte cells at the end
+        let count_at = bytes.len() - 3 * 10 - 4;
+        assert_eq!(bytes[count_at..count_at + 4], 3u32.to_le_bytes());
+        bytes[count_at..count_at + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(Grid::load(bytes.as_slice()).is_err());
+    }
+}
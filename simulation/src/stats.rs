@@ -0,0 +1,286 @@
+// simulation/src/stats.rs
+
+use std::collections::HashMap;
+use crate::{CellState, DeathCause, Grid};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of population and cell-type counts, for tuning simulations
+#[derive(Clone, Debug, Default)]
+pub struct GridStats {
+    pub organism_count: usize,                  // All organisms, alive or not yet removed
+    pub alive_count: usize,                     // Organisms that are still alive
+    pub food_cells: usize,                      // Food cells on the grid
+    pub wall_cells: usize,                      // Wall cells on the grid
+    pub hazard_cells: usize,                    // Hazard cells on the grid
+    pub organism_cells: HashMap<CellState, usize>, // Living organism cells by type
+    pub avg_food_collected: f32,                // Average food collected per living organism
+    pub avg_lifetime: f32,                      // Average age of living organisms, in steps
+}
+
+impl GridStats {
+    /// Number of living organism cells of the given type
+    pub fn organism_cell_count(&self, state: CellState) -> usize {
+        self.organism_cells.get(&state).copied().unwrap_or(0)
+    }
+}
+
+/// Population and event counts for a single simulation step
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SimStats {
+    pub step: u64,                 // Step these counts belong to (1 for the first step)
+    pub organisms: usize,          // Organisms alive at the end of the step
+    pub births: usize,             // Offspring added during the step
+    pub deaths_old_age: usize,     // Organisms removed after reaching their lifespan
+    pub deaths_killed: usize,      // Organisms removed after losing all their health
+    pub deaths_starved: usize,     // Organisms removed after running out of food
+    pub food_cells: usize,         // Food cells at the end of the step
+    pub avg_organism_size: f32,    // Average cell count of living organisms
+}
+
+/// Deaths by cause since the grid was created or last reset
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DeathStats {
+    pub old_age: u64,              // Organisms that reached their lifespan
+    pub killed: u64,               // Organisms that lost all their health to killers or hazards
+    pub starved: u64,              // Organisms that ran out of energy or food
+}
+
+impl DeathStats {
+    pub fn record(&mut self, cause: DeathCause) {
+        match cause {
+            DeathCause::OldAge => self.old_age += 1,
+            DeathCause::Killed => self.killed += 1,
+            DeathCause::Starved => self.starved += 1,
+        }
+    }
+    
+    pub fn total(&self) -> u64 {
+        self.old_age + self.killed + self.starved
+    }
+}
+
+/// End-of-run totals from `run_headless`
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RunSummary {
+    pub final_organisms: usize,    // Organisms alive after the last step
+    pub peak_organisms: usize,     // Most organisms alive at once, including before the first step
+    pub total_births: usize,       // Offspring added during the run
+    pub total_deaths: usize,       // Organisms removed during the run, for any reason
+    pub extinct_at: Option<u64>,   // Grid step at which the population first hit zero
+}
+
+/// Run `steps` steps without rendering and summarize how the population fared
+pub fn run_headless(grid: &mut Grid, steps: u32) -> RunSummary {
+    let alive = grid.organisms.iter().filter(|org| org.is_alive).count();
+    let mut summary = RunSummary {
+        final_organisms: alive,
+        peak_organisms: alive,
+        ..RunSummary::default()
+    };
+    
+    for _ in 0..steps {
+        grid.step();
+        let stats = grid.sim_stats();
+        summary.total_births += stats.births;
+        summary.total_deaths += stats.deaths_old_age + stats.deaths_killed + stats.deaths_starved;
+        summary.peak_organisms = summary.peak_organisms.max(stats.organisms);
+        if stats.organisms == 0 && summary.extinct_at.is_none() {
+            summary.extinct_at = Some(stats.step);
+        }
+        summary.final_organisms = stats.organisms;
+    }
+    summary
+}
+
+impl Grid {
+    /// Counts for the most recently completed step
+    pub fn sim_stats(&self) -> &SimStats {
+        &self.sim_stats
+    }
+    
+    /// Deaths by cause since the grid was created or last reset
+    ///
+    /// ```
+    /// use simulation::{CellState, Grid, Organism};
+    ///
+    /// let mut grid = Grid::new_seeded(20, 20, 1).unwrap();
+    /// grid.food_production_prob = 0.0;
+    /// grid.insta_kill = true;
+    /// for x in [8, 11] {
+    ///     let mut killer = Organism::new(0, x, 10);
+    ///     killer.add_cell(CellState::Killer, if x == 8 { 1 } else { -1 }, 0);
+    ///     grid.add_organism(killer);
+    /// }
+    /// for _ in 0..3 {
+    ///     grid.step();
+    /// }
+    /// let deaths = grid.death_histogram();
+    /// assert_eq!(deaths.killed, 2);
+    /// assert_eq!(deaths.old_age, 0);
+    /// ```
+    pub fn death_histogram(&self) -> DeathStats {
+        self.death_stats
+    }
+    
+    /// Counts for recent steps, oldest first, capped at `stats_history_len` entries
+    pub fn stats_history(&self) -> &[SimStats] {
+        &self.stats_history
+    }
+    
+    /// Start counting a new step
+    pub(crate) fn begin_step_stats(&mut self) {
+        self.sim_stats = SimStats {
+            step: self.tick_count,
+            ..SimStats::default()
+        };
+    }
+    
+    /// Fill in the end-of-step totals and push the step onto the history
+    pub(crate) fn finish_step_stats(&mut self) {
+        let alive: Vec<usize> = self.organisms.iter()
+            .filter(|org| org.is_alive)
+            .map(|org| org.cells.len())
+            .collect();
+        self.sim_stats.organisms = alive.len();
+        if !alive.is_empty() {
+            self.sim_stats.avg_organism_size = alive.iter().sum::<usize>() as f32 / alive.len() as f32;
+        }
+        self.sim_stats.food_cells = self.food_cell_count();
+        
+        self.stats_history.push(self.sim_stats.clone());
+        if self.stats_history.len() > self.stats_history_len {
+            let excess = self.stats_history.len() - self.stats_history_len;
+            self.stats_history.drain(..excess);
+        }
+    }
+    
+    /// Compute population and cell-type counts in a single pass over cells and organisms
+    pub fn stats(&self) -> GridStats {
+        let mut stats = GridStats {
+            organism_count: self.organisms.len(),
+            ..GridStats::default()
+        };
+        
+        for cell in &self.cells {
+            match cell.state {
+                CellState::Food => stats.food_cells += 1,
+                CellState::Wall => stats.wall_cells += 1,
+                CellState::Hazard => stats.hazard_cells += 1,
+                _ => {}
+            }
+        }
+        
+        let mut total_food = 0u64;
+        let mut total_lifetime = 0u64;
+        for org in self.organisms.iter().filter(|org| org.is_alive) {
+            stats.alive_count += 1;
+            total_food += org.food_collected as u64;
+            total_lifetime += org.lifetime as u64;
+            for cell in &org.cells {
+                *stats.organism_cells.entry(cell.state).or_insert(0) += 1;
+            }
+        }
+        
+        if stats.alive_count > 0 {
+            stats.avg_food_collected = total_food as f32 / stats.alive_count as f32;
+            stats.avg_lifetime = total_lifetime as f32 / stats.alive_count as f32;
+        }
+        
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Organism;
+
+    #[test]
+    fn stats_count_a_hand_built_grid() {
+        let mut grid = Grid::new_seeded(10, 10, 1).unwrap();
+        for x in 0..3 {
+            grid.set_cell(x, 9, CellState::Food, None);
+        }
+        grid.set_cell(9, 0, CellState::Wall, None);
+        grid.set_cell(9, 1, CellState::Wall, None);
+        grid.set_cell(0, 5, CellState::Hazard, None);
+
+        grid.create_basic_organism(2, 2); // Mouth and two producers
+        let mut hunter = Organism::new(0, 7, 7);
+        hunter.add_cell(CellState::Mover, 1, 0);
+        hunter.add_cell(CellState::Killer, 0, 1);
+        assert!(grid.add_organism(hunter));
+        let mut dead = Organism::new(0, 5, 5);
+        dead.add_cell(CellState::Armor, 0, -1);
+        assert!(grid.add_organism(dead));
+        grid.organisms[2].is_alive = false;
+        grid.organisms[0].food_collected = 4;
+        grid.organisms[0].lifetime = 10;
+        grid.organisms[1].food_collected = 2;
+        grid.organisms[1].lifetime = 20;
+
+        let stats = grid.stats();
+        assert_eq!(stats.organism_count, 3);
+        assert_eq!(stats.alive_count, 2);
+        assert_eq!(stats.food_cells, 3);
+        assert_eq!(stats.wall_cells, 2);
+        assert_eq!(stats.hazard_cells, 1);
+        assert_eq!(stats.organism_cell_count(CellState::Mouth), 2);
+        assert_eq!(stats.organism_cell_count(CellState::Producer), 2);
+        assert_eq!(stats.organism_cell_count(CellState::Mover), 1);
+        assert_eq!(stats.organism_cell_count(CellState::Killer), 1);
+        assert_eq!(stats.organism_cell_count(CellState::Armor), 0); // Only on the dead organism
+        assert_eq!(stats.organism_cells.values().sum::<usize>(), 6);
+        assert_eq!(stats.avg_food_collected, 3.0);
+        assert_eq!(stats.avg_lifetime, 15.0);
+    }
+
+    fn headless_run(seed: u64) -> (RunSummary, Grid) {
+        let mut grid = Grid::new_seeded(50, 50, seed).unwrap();
+        grid.food_production_prob = 2.0;
+        grid.origin_of_life();
+        (run_headless(&mut grid, 1000), grid)
+    }
+
+    #[test]
+    fn headless_run_summarizes_the_population() {
+        let (summary, grid) = headless_run(1);
+        assert_eq!(grid.tick_count(), 1000);
+        assert_eq!(summary.final_organisms, grid.organisms.iter().filter(|org| org.is_alive).count());
+        assert!(summary.total_births > 0);
+        assert!(summary.total_deaths > 0);
+        // Everyone alive at the end is the first organism or a birth nobody undid
+        assert_eq!(summary.final_organisms + summary.total_deaths, 1 + summary.total_births);
+        assert!(summary.peak_organisms >= summary.final_organisms);
+        assert_eq!(summary.extinct_at, None);
+
+        // A fixed seed gives the same run every time
+        let fields = |summary: &RunSummary| (summary.final_organisms, summary.peak_organisms,
+            summary.total_births, summary.total_deaths, summary.extinct_at);
+        assert_eq!(fields(&headless_run(1).0), fields(&summary));
+    }
+
+    #[test]
+    fn headless_run_reports_when_the_population_dies_out() {
+        let mut grid = Grid::new_seeded(50, 50, 1).unwrap();
+        grid.food_production_prob = 0.0;
+        grid.origin_of_life(); // Three cells, so it dies of old age after 300 steps
+        let summary = run_headless(&mut grid, 1000);
+        assert_eq!(summary.extinct_at, Some(300));
+        assert_eq!((summary.final_organisms, summary.peak_organisms), (0, 1));
+        assert_eq!((summary.total_births, summary.total_deaths), (0, 1));
+    }
+
+    #[test]
+    fn stats_of_an_empty_grid_are_zero() {
+        let stats = Grid::new_seeded(10, 10, 1).unwrap().stats();
+        assert_eq!((stats.organism_count, stats.alive_count, stats.food_cells), (0, 0, 0));
+        assert!(stats.organism_cells.is_empty());
+        assert_eq!(stats.avg_food_collected, 0.0);
+        assert_eq!(stats.avg_lifetime, 0.0);
+    }
+}